@@ -1,38 +1,58 @@
+use std::convert::TryFrom;
 use std::str::FromStr;
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_slice, to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128, WasmMsg, ensure_eq, CosmosMsg, Storage,
+    from_slice, to_binary, Addr, Binary, Decimal, Decimal256, Uint256, Deps, DepsMut, Empty, Env, MessageInfo,
+    Reply, Response, StdError, StdResult, SubMsg, SubMsgResult, Uint128, WasmMsg, ensure_eq, CosmosMsg, Storage, Order, Api,
 };
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg, TokenInfoResponse};
+use cw20_base::allowances::{deduct_allowance, execute_decrease_allowance, execute_increase_allowance, query_allowance};
 
-use cw2::set_contract_version;
-use cw_utils::{ensure_from_older_version, maybe_addr};
+use cw2::{get_contract_version, set_contract_version};
+use cw_core_interface::voting::{InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_controllers::PausableOperation;
+use cw_storage_plus::Bound;
+use cw_utils::{ensure_from_older_version, maybe_addr, Expiration};
+use semver::Version;
 
 use crate::error::ContractError;
+use crate::hook::{MemberChangedHookMsg, MemberDiff};
+use crate::v1;
 use crate::msg::{
-    ExecuteMsg, MigrateMsg, QueryMsg, Cw20HookMsg, WithdrawMsg, AdminResponse, ConfigResponse, TotalStakedResponse, RewardResponse, StakedResponse,
+    ExecuteMsg, MigrateMsg, QueryMsg, Cw20HookMsg, WithdrawMsg, AdminResponse, ConfigResponse, TotalStakedResponse, RewardResponse, StakedResponse, SyneDistributionResponse,
+    RewardStreamResponse, RewardStreamsResponse, StreamRewardsResponse, StakerResponse, StakersResponse, StatsResponse,
+    FeeRecipientMsg, PendingFee, PendingFeesResponse, RewardRateResponse, RewardWindow, DelegatedWithdrawalResponse,
 };
 use crate::queriers::query_loop_protocol_staking_rewards;
 use crate::state::{
-    Config, TokenInfo, ADMIN, CONFIG, STAKE, TOTAL_STAKED, REWARD_ACTION, RewardAction,
+    Config, TokenInfo, ADMIN, CONFIG, OWNERSHIP_PROPOSAL, PAUSABLE, STAKE, TOTAL_STAKED, PENDING_ACTIONS, NEXT_HARVEST_ID, RewardAction, SyneDistributionConfig,
+    CurrentSyneDistribution, SYNE_DISTRIBUTION_CONFIG, CURRENT_SYNE_DISTRIBUTION, SYNE_POWER_DIFF,
+    RewardStream, REWARD_STREAMS, STREAM_POWER_DIFF, CLAIMS, HOOKS, StakingInfo, VaultStats, STATS, AUTO_COMPOUND,
+    COMPOUND_CURSOR, FeeRecipient, PENDING_FEES, RewardPoint, DAY_REWARDS, WEEK_REWARDS, MONTH_REWARDS,
+    REWARDS_RECORDED, HOUR, HALF_DAY, DAY, WITHDRAW_DELEGATES, RewardSwap,
 };
 
 use synedao::bloop_vault::InstantiateMsg;
+use synedex::asset::{AssetInfo, AssetInfoExt, AssetInfoValidated};
+use synedex::multi_hop::{Cw20HookMsg as MultiHopCw20HookMsg, SwapOperation};
 
 // version info for migration info
 const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_CRATE_NAME"));
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const SECONDS_PER_DAY: u64 = 86400;
+/// Every stake change settles all reward streams, so their number is bounded
+const MAX_REWARD_STREAMS: u32 = 10;
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -44,22 +64,22 @@ pub fn instantiate(
     // min_bond is at least 1, so 0 stake -> non-membership
     let min_bond = std::cmp::max(msg.min_bond, Uint128::new(1));
 
-    TOTAL_STAKED.save(deps.storage, &TokenInfo::default())?;
+    TOTAL_STAKED.save(deps.storage, &TokenInfo::default(), env.block.height)?;
 
     let config = Config {
-        admin: deps.api.addr_validate(&msg.admin)?,
         token: deps.api.addr_validate(&msg.token)?,
         bloop_converter_and_staker: info.sender,
         min_bond,
         loop_protocol_staking: deps.api.addr_validate(&msg.loop_protocol_staking)?,
-        treasury_wallet: None,
-        treasury_withdrawer: None,
-        syne_staking_reward_distributor: None,
-        treasury_fee: Decimal::from_str("0.2").unwrap(),
-        syne_staking_fee: Decimal::zero(),
+        fee_recipients: vec![FeeRecipient {
+            address: deps.api.addr_validate(&msg.admin)?,
+            share: Decimal::from_str("0.2").unwrap(),
+            label: "treasury".to_string(),
+        }],
         total_fee_cap: Decimal::from_str("0.3").unwrap(),
-        treasury_fee_limit: Decimal::from_str("0.05").unwrap(),
         duration: 12u64,
+        unbonding_period: 0,
+        swap_router: None,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -77,50 +97,109 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     let api = deps.api;
     match msg {
-        ExecuteMsg::UpdateAdmin { admin } => {
-            Ok(ADMIN.execute_update_admin(deps, info, maybe_addr(api, admin)?)?)
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let admin = load_admin(deps.as_ref())?;
+            Ok(OWNERSHIP_PROPOSAL.execute_propose_new_owner(deps, info, &env, &admin, owner, expires_in)?)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let admin = load_admin(deps.as_ref())?;
+            Ok(OWNERSHIP_PROPOSAL.execute_drop_ownership_proposal(deps, info, &admin)?)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            Ok(OWNERSHIP_PROPOSAL.execute_claim_ownership(deps, info, &env, |deps, admin| {
+                ADMIN.set(deps, Some(admin))
+            })?)
         }
         ExecuteMsg::UpdateConfig { 
             min_bond, 
-            treasury_wallet, 
-            treasury_withdrawer, 
-            syne_staking_reward_distributor, 
-            treasury_fee, 
-            syne_staking_fee 
+            fee_recipients,
+            unbonding_period,
+            swap_router,
         } => execute_update_config(
             deps, 
             env, 
             info, 
             min_bond, 
-            treasury_wallet, 
-            treasury_withdrawer, 
-            syne_staking_reward_distributor, 
-            treasury_fee, 
-            syne_staking_fee
+            fee_recipients,
+            unbonding_period,
+            swap_router,
         ),
         ExecuteMsg::Receive(msg) => execute_receive_cw20(deps, env, info, msg),
-        ExecuteMsg::WithdrawRewards { address } => execute_withdraw_rewards(deps, env, info, address),
-        ExecuteMsg::WithdrawTreasuryRewards { amount } => execute_withdraw_treasury_rewards(deps, env, info, amount),
-        ExecuteMsg::WithdrawSyneStakingRewards { amount } => execute_withdraw_syne_staking_rewards(deps, env, info, amount),
-        ExecuteMsg::Unstake { amount } => execute_unstake(deps, info, amount),
+        ExecuteMsg::WithdrawRewards { owner, receiver } => execute_withdraw_rewards(deps, env, info, owner, receiver),
+        ExecuteMsg::DelegateWithdrawal { delegated } => execute_delegate_withdrawal(deps, info, delegated),
+        ExecuteMsg::WithdrawRewardsAs { ask_asset, max_spread, operations, minimum_receive } => {
+            execute_withdraw_rewards_as(deps, env, info, ask_asset, max_spread, operations, minimum_receive)
+        }
+        ExecuteMsg::FlushFees {} => execute_flush_fees(deps, env),
+        ExecuteMsg::Unstake { amount } => execute_unstake(deps, env, info, amount),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::SetAutoCompound { enabled } => execute_set_auto_compound(deps, info, enabled),
+        ExecuteMsg::Compound {} => execute_compound(deps, env),
         ExecuteMsg::DistributeRewards { address } => execute_distribute_user_rewards(deps, env, info, address),
+        ExecuteMsg::UpdateSyneDistribution { syne_addr, distribution_per_day } => execute_update_syne_distribution(deps, env, info, syne_addr, distribution_per_day),
+        ExecuteMsg::AddRewardStream { asset } => execute_add_reward_stream(deps, env, info, asset),
+        ExecuteMsg::FundRewards { duration } => execute_fund_rewards(deps, env, info, duration),
+        ExecuteMsg::Pause { operations } => {
+            let is_admin = ADMIN.is_admin(deps.as_ref(), &info.sender)?;
+            Ok(PAUSABLE.execute_pause(deps, info, is_admin, operations)?)
+        }
+        ExecuteMsg::Unpause { operations } => {
+            let is_admin = ADMIN.is_admin(deps.as_ref(), &info.sender)?;
+            Ok(PAUSABLE.execute_unpause(deps, info, is_admin, operations)?)
+        }
+        ExecuteMsg::UpdateGuardian { guardian } => {
+            let is_admin = ADMIN.is_admin(deps.as_ref(), &info.sender)?;
+            Ok(PAUSABLE.execute_update_guardian(deps, info, is_admin, maybe_addr(api, guardian)?)?)
+        }
+        ExecuteMsg::AddHook { addr } => {
+            Ok(HOOKS.execute_add_hook(&ADMIN, deps, info, api.addr_validate(&addr)?)?)
+        }
+        ExecuteMsg::RemoveHook { addr } => {
+            Ok(HOOKS.execute_remove_hook(&ADMIN, deps, info, api.addr_validate(&addr)?)?)
+        }
+        ExecuteMsg::Transfer { recipient, amount } => {
+            let recipient = api.addr_validate(&recipient)?;
+            execute_transfer_shares(deps, env, info.sender, recipient, amount, None)
+        }
+        ExecuteMsg::Send { contract, amount, msg } => {
+            let contract = api.addr_validate(&contract)?;
+            let receive = Cw20ReceiveMsg { sender: info.sender.to_string(), amount, msg };
+            execute_transfer_shares(deps, env, info.sender, contract, amount, Some(receive))
+        }
+        ExecuteMsg::TransferFrom { owner, recipient, amount } => {
+            let owner = api.addr_validate(&owner)?;
+            let recipient = api.addr_validate(&recipient)?;
+            deduct_allowance(deps.storage, &owner, &info.sender, &env.block, amount)?;
+            execute_transfer_shares(deps, env, owner, recipient, amount, None)
+        }
+        ExecuteMsg::SendFrom { owner, contract, amount, msg } => {
+            let owner = api.addr_validate(&owner)?;
+            let contract = api.addr_validate(&contract)?;
+            deduct_allowance(deps.storage, &owner, &info.sender, &env.block, amount)?;
+            let receive = Cw20ReceiveMsg { sender: info.sender.to_string(), amount, msg };
+            execute_transfer_shares(deps, env, owner, contract, amount, Some(receive))
+        }
+        ExecuteMsg::IncreaseAllowance { spender, amount, expires } => {
+            Ok(execute_increase_allowance(deps, env, info, spender, amount, expires)?)
+        }
+        ExecuteMsg::DecreaseAllowance { spender, amount, expires } => {
+            Ok(execute_decrease_allowance(deps, env, info, spender, amount, expires)?)
+        }
     }
 }
 
 pub fn execute_update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     min_bond: Option<Uint128>,
-    treasury_wallet: Option<String>,
-    treasury_withdrawer: Option<String>,
-    syne_staking_reward_distributor: Option<String>,
-    treasury_fee: Option<Decimal>,
-    syne_staking_fee: Option<Decimal>,
+    fee_recipients: Option<Vec<FeeRecipientMsg>>,
+    unbonding_period: Option<u64>,
+    swap_router: Option<String>,
 ) -> Result<Response, ContractError> {
-    let mut cfg = CONFIG.load(deps.storage)?;
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
-    ensure_eq!(info.sender, cfg.admin, ContractError::Unauthorized {});
+    let mut cfg = CONFIG.load(deps.storage)?;
 
     let mut valid = false;
 
@@ -128,46 +207,37 @@ pub fn execute_update_config(
         .add_attribute("action", "update_config");
 
     if let Some(min_bond) = min_bond {
+        // 0 stake never makes a member
+        let min_bond = std::cmp::max(min_bond, Uint128::new(1));
+        res = change_min_bond(deps.storage, &env, &cfg, min_bond, res)?;
         cfg.min_bond = min_bond;
         valid = true;
         res = res.add_attribute("min_bond", &cfg.min_bond.to_string());
     }
 
-    if let Some(treasury_wallet) = treasury_wallet {
-        cfg.treasury_wallet = Some(deps.api.addr_validate(&treasury_wallet)?);
-        valid = true;
-        res = res.add_attribute("treasury_wallet", &treasury_wallet.to_string());
-    }
-
-    if let Some(treasury_withdrawer) = treasury_withdrawer {
-        cfg.treasury_withdrawer = Some(deps.api.addr_validate(&treasury_withdrawer)?);
-        valid = true;
-        res = res.add_attribute("treasury_withdrawer", &treasury_withdrawer.to_string());
-    }
-
-    if let Some(syne_staking_reward_distributor) = syne_staking_reward_distributor {
-        cfg.syne_staking_reward_distributor = Some(deps.api.addr_validate(&syne_staking_reward_distributor)?);
+    if let Some(fee_recipients) = fee_recipients {
+        cfg.fee_recipients = validate_fee_recipients(deps.api, fee_recipients, cfg.total_fee_cap)?;
         valid = true;
-        res = res.add_attribute("syne_staking_reward_distributor", &syne_staking_reward_distributor.to_string());
+        res = res.add_attribute("fee_recipients", cfg.fee_recipients.len().to_string());
     }
 
-    if let Some(treasury_fee) = treasury_fee {
-        ensure_eq!(treasury_fee.gt(&cfg.treasury_fee_limit), true, ContractError::InvalidFee {});
-        cfg.treasury_fee = treasury_fee;
+    // only applies to unstakes from now on, pending claims keep their release time
+    if let Some(unbonding_period) = unbonding_period {
+        cfg.unbonding_period = unbonding_period;
         valid = true;
-        res = res.add_attribute("treasury_fee", &treasury_fee.to_string());
+        res = res.add_attribute("unbonding_period", unbonding_period.to_string());
     }
 
-    if let Some(syne_staking_fee) = syne_staking_fee {
-        cfg.syne_staking_fee = syne_staking_fee;
+    if let Some(swap_router) = swap_router {
+        cfg.swap_router = Some(deps.api.addr_validate(&swap_router)?);
         valid = true;
-        res = res.add_attribute("syne_staking_fee", &syne_staking_fee.to_string());
+        res = res.add_attribute("swap_router", swap_router);
     }
 
-    ensure_eq!(cfg.treasury_fee.checked_add(cfg.syne_staking_fee).unwrap().le(&cfg.total_fee_cap), true, ContractError::InvalidFee {});
-
     ensure_eq!(valid, true, ContractError::NoData {});
 
+    CONFIG.save(deps.storage, &cfg)?;
+
     Ok(res)
 }
 
@@ -183,11 +253,18 @@ pub fn execute_receive_cw20(
     // in the address's favor (like paying/bonding tokens, not withdrawls)
     let cfg = CONFIG.load(deps.storage)?;
 
-    // check token address is correct
-    ensure_eq!(info.sender, cfg.token, ContractError::InvalidAsset {});
-
     let msg: Cw20HookMsg = from_slice(&wrapper.msg)?;
+
+    // check token address is correct, funding is checked against the funded token
+    if matches!(msg, Cw20HookMsg::Stake {} | Cw20HookMsg::DistributeRewards { .. }) {
+        ensure_eq!(info.sender, cfg.token, ContractError::InvalidAsset {});
+    }
+
     match msg {
+        Cw20HookMsg::FundSyne {} => execute_fund_syne(deps, env, info, wrapper),
+        Cw20HookMsg::FundRewards { duration } => {
+            fund_reward_stream(deps.storage, &env, AssetInfoValidated::Token(info.sender), wrapper.amount, duration)
+        },
         Cw20HookMsg::Stake {} => execute_stake(deps, env, info, wrapper),
         Cw20HookMsg::DistributeRewards { address } => execute_distribute_rewards(deps, env, info, address, wrapper),
     }
@@ -195,416 +272,892 @@ pub fn execute_receive_cw20(
 
 pub fn execute_stake(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
-    let cfg = CONFIG.load(deps.storage)?;
-    let storage = deps.storage;
-    let api = deps.api;
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Deposit)?;
 
     // user's address and staking amount
-    let amount = msg.amount;
-    let sender = msg.sender;
-
-    // check if loop reward is existing
-    let user_reward_reponse = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking, cfg.bloop_converter_and_staker.clone())?;
-    let withdrawable_amount = user_reward_reponse.user_reward.checked_add(user_reward_reponse.pending_reward).unwrap();
-    if withdrawable_amount.gt(&Uint128::zero()) {
-        let mut reward_action = REWARD_ACTION.load(storage).unwrap_or(RewardAction::NoAction {});
-        match reward_action {
-            RewardAction::NoAction {} => {
-                reward_action = RewardAction::Stake { address: deps.api.addr_validate(&sender)?, amount };
-        
-                let messages = vec![
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.bloop_converter_and_staker.clone().to_string(),
-                        msg: to_binary(&WithdrawMsg::WithdrawRewards {})?,
-                        funds: vec![],
-                    }),
-                ];
-            
-                REWARD_ACTION.save(storage, &reward_action)?;
-            
-                Ok(Response::new().add_messages(messages))
-            },
-            _ => {Err(ContractError::InvalidAction {})}
-        }
-    } else {
-        let reward_amount = add_stake(storage, api.addr_validate(&sender)?, amount)?;
-        let mut res: Response = Response::new()
-            .add_attribute("Stake", amount.to_string())
-            .add_attribute("From", sender.clone());
-        if reward_amount.gt(&Uint128::zero()) {
-            let msgs = vec![
-                CosmosMsg::Wasm(WasmMsg::Execute {
-                    //sending reward to user
-                    contract_addr: cfg.token.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: sender, amount: reward_amount })?,
-                    funds: vec![],
-                }),
-            ];
-            res = res.add_messages(msgs);
-        }
-        Ok(res)
-    }
+    let address = deps.api.addr_validate(&msg.sender)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    let stake = STAKE.may_load(deps.storage, &address)?.unwrap_or_default().stake;
+    ensure_eq!((stake.checked_add(msg.amount)? >= cfg.min_bond), true, ContractError::BelowMinBond(cfg.min_bond));
+    harvest_then(deps, &env, RewardAction::Stake { address, amount: msg.amount })
 }
 
 pub fn execute_withdraw_rewards(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    address: String,
+    env: Env,
+    info: MessageInfo,
+    owner: Option<String>,
+    receiver: Option<String>,
 ) -> Result<Response, ContractError> {
-    let cfg = CONFIG.load(deps.storage)?;
-    let storage = deps.storage;
-    let api = deps.api;
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Claim)?;
 
-    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking, cfg.bloop_converter_and_staker.clone())?;
-    let withdrawable_amount = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
-    if withdrawable_amount.gt(&Uint128::zero()) {
-        let mut reward_action = REWARD_ACTION.load(storage).unwrap_or(RewardAction::NoAction {});
-        match reward_action {
-            RewardAction::NoAction {} => {
-                reward_action = RewardAction::Reward { address: deps.api.addr_validate(&address)? };
-        
-                let messages = vec![
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.bloop_converter_and_staker.clone().to_string(),
-                        msg: to_binary(&WithdrawMsg::WithdrawRewards {})?,
-                        funds: vec![],
-                    }),
-                ];
-        
-                REWARD_ACTION.save(storage, &reward_action)?;
-        
-                Ok(Response::new().add_messages(messages))
-            },
-            _ => {Err(ContractError::InvalidAction {})}
-        }
-    } else {
-        let reward_amount = update_rewards(storage, api.addr_validate(&address)?)?;
-        let res = Response::new()
-            .add_attribute("Send", reward_amount.to_string())
-            .add_attribute("To", &address.to_string())
-            .add_message(
-                CosmosMsg::Wasm(WasmMsg::Execute {
-                    //sending reward to user
-                    contract_addr: cfg.token.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: address.to_string(), amount: reward_amount })?,
-                    funds: vec![],
-                }),
-            );
-        Ok(res)
-    }
-}
-
-pub fn execute_withdraw_treasury_rewards(
+    let owner = maybe_addr(deps.api, owner)?.unwrap_or_else(|| info.sender.clone());
+    if owner != info.sender && WITHDRAW_DELEGATES.may_load(deps.storage, &owner)?.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let receiver = maybe_addr(deps.api, receiver)?.unwrap_or(info.sender);
+    harvest_then(deps, &env, RewardAction::Reward { address: owner, receiver: Some(receiver), swap: None })
+}
+
+pub fn execute_withdraw_rewards_as(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    amount: Option<Uint128>,
+    ask_asset: AssetInfo,
+    max_spread: Option<Decimal>,
+    operations: Option<Vec<SwapOperation>>,
+    minimum_receive: Uint128,
 ) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Claim)?;
     let cfg = CONFIG.load(deps.storage)?;
-    let storage = deps.storage;
-
-    ensure_eq!(info.sender, cfg.treasury_withdrawer.clone().unwrap(), ContractError::Unauthorized {});
-    
-    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking.clone(), cfg.bloop_converter_and_staker.clone())?;
-    let withdrawable_amount = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
-
-    CONFIG.save(storage, &cfg.clone())?;
-    
-    if withdrawable_amount.gt(&Uint128::zero()) {    
-        let mut reward_action = REWARD_ACTION.load(storage).unwrap_or(RewardAction::NoAction {});
-        match reward_action {
-            RewardAction::NoAction {} => {
-                reward_action = RewardAction::TreasuryWithdraw { amount };
-        
-                let messages = vec![
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.bloop_converter_and_staker.clone().to_string(),
-                        msg: to_binary(&WithdrawMsg::WithdrawRewards {})?,
-                        funds: vec![],
-                    }),
-                ];
-        
-                REWARD_ACTION.save(storage, &reward_action)?;
-        
-                Ok(Response::new().add_messages(messages))
-            },
-            _ => {Err(ContractError::InvalidAction {})}
-        }
-    } else {
-        let treasury_amount = update_treasury_amounts(storage, info, amount)?;
-        ensure_eq!((treasury_amount.gt(&Uint128::zero())), true, ContractError::NoWithdrawable {});
+    ensure_eq!(cfg.swap_router.is_some(), true, ContractError::NoSwapRouter {});
+
+    let token = AssetInfo::Token(cfg.token.to_string());
+    let operations = operations.unwrap_or_else(|| {
+        vec![SwapOperation::SynedexSwap { offer_asset_info: token.clone(), ask_asset_info: ask_asset.clone() }]
+    });
+    let first_offer = match operations.first() {
+        Some(SwapOperation::SynedexSwap { offer_asset_info, .. }) => offer_asset_info.clone(),
+        None => return Err(ContractError::InvalidSwapRoute {}),
+    };
+    let last_ask = operations.last().map(|operation| operation.get_target_asset_info());
+    ensure_eq!(first_offer, token, ContractError::InvalidSwapRoute {});
+    ensure_eq!(last_ask, Some(ask_asset), ContractError::InvalidSwapRoute {});
 
-        let res = Response::new()
-            .add_attribute("Send", treasury_amount.to_string())
-            .add_attribute("To", &cfg.treasury_wallet.clone().unwrap().to_string())
-            .add_message(
-                CosmosMsg::Wasm(WasmMsg::Execute {
-                    //sending reward to user
-                    contract_addr: cfg.token.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: cfg.treasury_wallet.clone().unwrap().to_string(), amount: treasury_amount })?,
-                    funds: vec![],
-                }),
-            );
-        Ok(res)
-    }
+    let swap = RewardSwap { operations, minimum_receive, max_spread };
+    harvest_then(deps, &env, RewardAction::Reward { address: info.sender, receiver: None, swap: Some(swap) })
 }
 
-pub fn execute_withdraw_syne_staking_rewards(
+pub fn execute_delegate_withdrawal(
     deps: DepsMut,
-    _env: Env,
     info: MessageInfo,
-    amount: Option<Uint128>,
+    delegated: String,
 ) -> Result<Response, ContractError> {
-    let cfg = CONFIG.load(deps.storage)?;
-    let storage = deps.storage;
-    
-    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking, cfg.bloop_converter_and_staker.clone())?;
-    let withdrawable_amount = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
-    
-    if withdrawable_amount.gt(&Uint128::zero()) {    
-        let mut reward_action = REWARD_ACTION.load(storage).unwrap_or(RewardAction::NoAction {});
-        match reward_action {
-            RewardAction::NoAction {} => {
-                reward_action = RewardAction::SyneStakingRewardWithdraw { amount };
-        
-                let messages = vec![
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.bloop_converter_and_staker.clone().to_string(),
-                        msg: to_binary(&WithdrawMsg::WithdrawRewards {})?,
-                        funds: vec![],
-                    }),
-                ];
-        
-                REWARD_ACTION.save(storage, &reward_action)?;
-        
-                Ok(Response::new().add_messages(messages))
-            },
-            _ => {Err(ContractError::InvalidAction {})}
-        }
+    let delegated = deps.api.addr_validate(&delegated)?;
+    if delegated == info.sender {
+        WITHDRAW_DELEGATES.remove(deps.storage, &info.sender);
     } else {
-        let syne_staking_reward_amount = update_syne_staking_amounts(storage, info, amount)?;
-        ensure_eq!((syne_staking_reward_amount.gt(&Uint128::zero())), true, ContractError::NoWithdrawable {});
-
-        if let Some(syne_staking_reward_distributor) = cfg.syne_staking_reward_distributor {
-            let res = Response::new()
-                .add_attribute("Send", syne_staking_reward_amount.to_string())
-                .add_attribute("To", &syne_staking_reward_distributor.to_string())
-                .add_message(
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.token.to_string(),
-                        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: syne_staking_reward_distributor.to_string(), amount: syne_staking_reward_amount })?,
-                        funds: vec![],
-                    }),
-                );
-            Ok(res)
-        } else {
-            Err(ContractError::InvalidDistributor {})
+        WITHDRAW_DELEGATES.save(deps.storage, &info.sender, &delegated)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "delegate_withdrawal")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("delegated", delegated.to_string()))
+}
+
+/// Most fee recipients, all of them are paid by one `FlushFees {}`
+const MAX_FEE_RECIPIENTS: usize = 10;
+
+fn validate_fee_recipients(
+    api: &dyn Api,
+    recipients: Vec<FeeRecipientMsg>,
+    total_fee_cap: Decimal,
+) -> Result<Vec<FeeRecipient>, ContractError> {
+    ensure_eq!((recipients.len() <= MAX_FEE_RECIPIENTS), true, ContractError::TooManyFeeRecipients(MAX_FEE_RECIPIENTS));
+
+    let mut validated: Vec<FeeRecipient> = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        if validated.iter().any(|other| other.label == recipient.label) {
+            return Err(ContractError::DuplicateFeeLabel(recipient.label));
         }
+        validated.push(FeeRecipient {
+            address: api.addr_validate(&recipient.address)?,
+            share: recipient.share,
+            label: recipient.label,
+        });
     }
+    let total_fee = validated.iter().try_fold(Decimal::zero(), |total, recipient| total.checked_add(recipient.share))?;
+    ensure_eq!(total_fee.le(&total_fee_cap), true, ContractError::InvalidFee {});
+
+    Ok(validated)
 }
 
-pub fn execute_distribute_user_rewards(
+/// Part of `reward` taken by a fee recipient with `share`, rounded down
+fn fee_of(reward: Uint128, share: Decimal) -> Result<Uint128, ContractError> {
+    mul_floor(reward, share.into())
+}
+
+/// `amount * ratio` rounded down, erroring instead of wrapping or panicking
+fn mul_floor(amount: Uint128, ratio: Decimal256) -> Result<Uint128, ContractError> {
+    let scaled = Uint256::from(amount)
+        .checked_mul(ratio.atomics())
+        .map_err(ContractError::RewardOverflow)?;
+    Ok(Uint128::try_from(scaled / Decimal256::one().atomics())?)
+}
+
+/// `amount` shared over `staked` as a per-share index step, rounded down
+fn per_share(amount: Uint128, staked: Uint128) -> Result<Decimal256, ContractError> {
+    let scaled = Uint256::from(amount)
+        .checked_mul(Decimal256::one().atomics())
+        .map_err(ContractError::RewardOverflow)?;
+    Ok(Decimal256::new(scaled.checked_div(Uint256::from(staked)).map_err(StdError::from)?))
+}
+
+/// Rewards earned by `stake` since the index was at `power_diff`
+fn earned_rewards(power: Decimal256, power_diff: Decimal256, stake: Uint128) -> Result<Uint128, ContractError> {
+    let diff = power.checked_sub(power_diff).map_err(ContractError::RewardOverflow)?;
+    mul_floor(stake, diff)
+}
+
+pub fn execute_flush_fees(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    address: Addr,
+    env: Env,
+) -> Result<Response, ContractError> {
+    harvest_then(deps, &env, RewardAction::FlushFees {})
+}
+
+/// Runs `action` once the rewards waiting in the converter are distributed, so it settles
+/// against an up to date reward index. The harvest is a submessage and `action` runs in its
+/// reply, within the same transaction.
+fn harvest_then(
+    deps: DepsMut,
+    env: &Env,
+    action: RewardAction,
 ) -> Result<Response, ContractError> {
     let cfg = CONFIG.load(deps.storage)?;
-    let storage = deps.storage;
 
-    let mut res = Response::new();
-    res = res
-        .add_attribute("Action", "send_rewards");
-
-    let reward_amount = update_rewards(storage, address.clone())?;
-    if reward_amount.gt(&Uint128::zero()) {
-        res = res
-            .add_attribute("Send", reward_amount.to_string())
-            .add_attribute("To", &address.to_string())
-            .add_message(
-                CosmosMsg::Wasm(WasmMsg::Execute {
-                    //sending reward to user
-                    contract_addr: cfg.token.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: address.to_string(), amount: reward_amount })?,
-                    funds: vec![],
-                }),
-            );
+    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking.clone(), cfg.bloop_converter_and_staker.clone())?;
+    let withdrawable_amount = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward)?;
+    if withdrawable_amount.is_zero() {
+        return apply_reward_action(deps.storage, env, &cfg, action);
+    }
+
+    let id = NEXT_HARVEST_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_HARVEST_ID.save(deps.storage, &id.wrapping_add(1))?;
+    PENDING_ACTIONS.save(deps.storage, id, &action)?;
+
+    let harvest = WasmMsg::Execute {
+        contract_addr: cfg.bloop_converter_and_staker.to_string(),
+        msg: to_binary(&WithdrawMsg::WithdrawRewards {})?,
+        funds: vec![],
+    };
+    Ok(Response::new().add_submessage(SubMsg::reply_always(harvest, id)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let action = PENDING_ACTIONS
+        .may_load(deps.storage, msg.id)?
+        .ok_or(ContractError::UnknownReply(msg.id))?;
+    PENDING_ACTIONS.remove(deps.storage, msg.id);
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let mut res = apply_reward_action(deps.storage, &env, &cfg, action)?;
+
+    // a failed harvest must not lock the stakers out, the next one picks up the rewards
+    if let SubMsgResult::Err(err) = msg.result {
+        res = res.add_attribute("harvest_error", err);
     }
     Ok(res)
 }
 
-pub fn execute_distribute_rewards(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    address: Option<Addr>,
-    wrapper: Cw20ReceiveMsg,
+fn transfer_msg(token: &str, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount })?,
+        funds: vec![],
+    }))
+}
+
+/// Sends `amount` of the staked token to the swap router, which pays `receiver` what it swapped
+/// it into
+fn swap_msg(cfg: &Config, swap: RewardSwap, receiver: &Addr, amount: Uint128) -> Result<CosmosMsg, ContractError> {
+    let swap_router = cfg.swap_router.as_ref().ok_or(ContractError::NoSwapRouter {})?;
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: cfg.token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: swap_router.to_string(),
+            amount,
+            msg: to_binary(&MultiHopCw20HookMsg::ExecuteSwapOperations {
+                operations: swap.operations,
+                minimum_receive: Some(swap.minimum_receive),
+                receiver: Some(receiver.to_string()),
+                max_spread: swap.max_spread,
+                referral_address: None,
+                referral_commission: None,
+            })?,
+        })?,
+        funds: vec![],
+    }))
+}
+
+/// The part of `stake` that earns rewards and counts as membership, nothing below `min_bond`
+fn bonded(stake: Uint128, min_bond: Uint128) -> Uint128 {
+    if stake >= min_bond {
+        stake
+    } else {
+        Uint128::zero()
+    }
+}
+
+fn bonded_stake(storage: &dyn Storage, address: &Addr) -> StdResult<Uint128> {
+    let stake = STAKE.may_load(storage, address)?.unwrap_or_default().stake;
+    Ok(bonded(stake, CONFIG.load(storage)?.min_bond))
+}
+
+/// Sum of the stakes at or above `min_bond`
+fn bonded_total(storage: &dyn Storage, min_bond: Uint128) -> StdResult<Uint128> {
+    STAKE
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |total, item| Ok(total.checked_add(bonded(item?.1.stake, min_bond))?))
+}
+
+/// Moves the bonded total from the minimum bond in `cfg` to `min_bond`. The stakers this moves
+/// across the bond are settled first, so a raise pays out what they earned so far and a cut
+/// does not hand them the rewards distributed before they counted. Goes over every staker.
+fn change_min_bond(
+    storage: &mut dyn Storage,
+    env: &Env,
+    cfg: &Config,
+    min_bond: Uint128,
+    mut res: Response,
 ) -> Result<Response, ContractError> {
-    let cfg = CONFIG.load(deps.storage)?;
-    let storage = deps.storage;
+    let crossing = STAKE
+        .range(storage, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, stake)) => bonded(stake.stake, cfg.min_bond).is_zero() != bonded(stake.stake, min_bond).is_zero(),
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut diffs = vec![];
+    for (address, old_stake) in crossing {
+        res = pay_syne(storage, env, &address, &address, res)?;
+        res = pay_streams(storage, env, &address, &address, res)?;
+        let reward_amount = update_rewards(storage, env, address.clone())?;
+        res = pay_rewards(storage, env, cfg, &address, reward_amount, res)?;
+
+        let new_stake = STAKE.load(storage, &address)?.stake;
+        let power = |stake: Uint128, min_bond: Uint128| {
+            let stake = bonded(stake, min_bond);
+            (!stake.is_zero()).then_some(stake)
+        };
+        diffs.push(MemberDiff::new(&address, power(old_stake.stake, cfg.min_bond), power(new_stake, min_bond)));
+    }
+
+    let mut total_stake = TOTAL_STAKED.load(storage)?;
+    total_stake.bonded = bonded_total(storage, min_bond)?;
+    TOTAL_STAKED.save(storage, &total_stake, env.block.height)?;
+
+    if diffs.is_empty() {
+        return Ok(res);
+    }
+    let hooks = HOOKS.prepare_hooks(storage, |hook| {
+        MemberChangedHookMsg::new(diffs.clone()).into_cosmos_msg(hook).map(SubMsg::new)
+    })?;
+    Ok(res.add_submessages(hooks))
+}
 
-    let mut reward_action = REWARD_ACTION.load(storage).unwrap_or(RewardAction::NoAction {});
+/// Tells every hook that the stake of `address` went from `old` to `new`.
+/// An address below the minimum bond is not a member.
+fn member_changed_msgs(
+    storage: &dyn Storage,
+    address: &Addr,
+    old: Uint128,
+    new: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    let min_bond = CONFIG.load(storage)?.min_bond;
+    let power = |stake: Uint128| {
+        let stake = bonded(stake, min_bond);
+        (!stake.is_zero()).then_some(stake)
+    };
+    let diff = MemberDiff::new(address, power(old), power(new));
+    HOOKS.prepare_hooks(storage, |hook| {
+        MemberChangedHookMsg::one(diff.clone()).into_cosmos_msg(hook).map(SubMsg::new)
+    })
+}
+
+pub fn apply_reward_action(
+    storage: &mut dyn Storage,
+    env: &Env,
+    cfg: &Config,
+    action: RewardAction,
+) -> Result<Response, ContractError> {
     let mut res = Response::new();
-    res = res
-        .add_attribute("Action", "distribute_rewards")
-        .add_attribute("Amount", wrapper.amount);
-    update_total_rewards(storage, wrapper.amount)?;
-    if let Some(address) = address {
-        reward_action = RewardAction::Reward { address };
-    }
-    match reward_action {
-        RewardAction::NoAction {} => {},
-        RewardAction::Reward { address } => {
-            let reward_amount = update_rewards(storage, address.clone())?;
+    match action {
+        RewardAction::Reward { address, receiver, swap } => {
+            let receiver = receiver.unwrap_or_else(|| address.clone());
+            res = pay_syne(storage, env, &address, &receiver, res)?;
+            res = pay_streams(storage, env, &address, &receiver, res)?;
+            // an asked for swap wins over compounding
+            if swap.is_none() && AUTO_COMPOUND.has(storage, &address) {
+                return compound(storage, env, &address, res);
+            }
+            let reward_amount = update_rewards(storage, env, address)?;
+            res = res.add_attribute("Action", "send_rewards");
             if reward_amount.gt(&Uint128::zero()) {
+                let msg = match swap {
+                    Some(swap) => swap_msg(cfg, swap, &receiver, reward_amount)?,
+                    None => transfer_msg(cfg.token.as_str(), &receiver, reward_amount)?,
+                };
                 res = res
                     .add_attribute("Send", reward_amount.to_string())
-                    .add_attribute("To", &address.to_string())
-                    .add_message(
-                        CosmosMsg::Wasm(WasmMsg::Execute {
-                            //sending reward to user
-                            contract_addr: cfg.token.to_string(),
-                            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: address.to_string(), amount: reward_amount })?,
-                            funds: vec![],
-                        }),
-                    );
+                    .add_attribute("To", receiver.to_string())
+                    .add_message(msg);
             }
         },
         RewardAction::Stake { address, amount } => {
-            let reward_amount = add_stake(storage, address.clone(), amount)?;
-            res = res.add_attribute("Stake", amount.to_string())
-                .add_attribute("From", address.clone().to_string());
-            if reward_amount.gt(&Uint128::zero()) {
-                let msgs = vec![
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.token.to_string(),
-                        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: address.to_string(), amount: reward_amount })?,
-                        funds: vec![],
-                    }),
-                ];
-                res = res.add_messages(msgs);
-            }
+            res = pay_syne(storage, env, &address, &address, res)?;
+            res = pay_streams(storage, env, &address, &address, res)?;
+            let old_stake = STAKE.may_load(storage, &address)?.unwrap_or_default().stake;
+            let reward_amount = add_stake(storage, env, address.clone(), amount)?;
+            res = pay_rewards(storage, env, cfg, &address, reward_amount, res)?;
+            let new_stake = STAKE.load(storage, &address)?.stake;
+            res = res
+                .add_attribute("Stake", amount.to_string())
+                .add_attribute("From", address.to_string())
+                .add_submessages(member_changed_msgs(storage, &address, old_stake, new_stake)?);
         },
-        RewardAction::TreasuryWithdraw { amount } => {
-            let treasury_amount = update_treasury_amounts(storage, info, amount)?;
+        RewardAction::Transfer { owner, recipient, amount, receive } => {
+            for address in [&owner, &recipient] {
+                res = pay_syne(storage, env, address, address, res)?;
+                res = pay_streams(storage, env, address, address, res)?;
+            }
+            let owner_old_stake = STAKE.may_load(storage, &owner)?.unwrap_or_default().stake;
+            let recipient_old_stake = STAKE.may_load(storage, &recipient)?.unwrap_or_default().stake;
+            let owner_rewards = remove_stake(storage, env, owner.clone(), amount)?;
+            let recipient_rewards = add_stake(storage, env, recipient.clone(), amount)?;
+            res = pay_rewards(storage, env, cfg, &owner, owner_rewards, res)?;
+            res = pay_rewards(storage, env, cfg, &recipient, recipient_rewards, res)?;
+            let owner_new_stake = STAKE.load(storage, &owner)?.stake;
+            let recipient_new_stake = STAKE.load(storage, &recipient)?.stake;
             res = res
-                .add_attribute("Send", treasury_amount.to_string())
-                .add_attribute("To", &cfg.treasury_wallet.clone().unwrap().to_string())
-                .add_message(
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.token.to_string(),
-                        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: cfg.treasury_wallet.clone().unwrap().to_string(), amount: treasury_amount })?,
-                        funds: vec![],
-                    }),
-                );
+                .add_attribute("Transfer", amount.to_string())
+                .add_attribute("From", owner.to_string())
+                .add_attribute("To", recipient.to_string())
+                .add_submessages(member_changed_msgs(storage, &owner, owner_old_stake, owner_new_stake)?)
+                .add_submessages(member_changed_msgs(storage, &recipient, recipient_old_stake, recipient_new_stake)?);
+            if let Some(receive) = receive {
+                res = res.add_message(receive.into_cosmos_msg(recipient)?);
+            }
         },
-        RewardAction::SyneStakingRewardWithdraw { amount } => {
-            let syne_staking_amount = update_syne_staking_amounts(storage, info, amount)?;
-            if let Some(syne_staking_reward_distributor) = cfg.syne_staking_reward_distributor {
+        RewardAction::FlushFees {} => {
+            let fees = PENDING_FEES
+                .range(storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            ensure_eq!((!fees.is_empty()), true, ContractError::NoWithdrawable {});
+
+            res = res.add_attribute("Action", "flush_fees");
+            for (address, amount) in fees {
+                PENDING_FEES.remove(storage, &address);
                 res = res
-                    .add_attribute("Send", syne_staking_amount.to_string())
-                    .add_attribute("To", &syne_staking_reward_distributor.to_string())
-                    .add_message(
-                        CosmosMsg::Wasm(WasmMsg::Execute {
-                            //sending reward to user
-                            contract_addr: cfg.token.to_string(),
-                            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: syne_staking_reward_distributor.to_string(), amount: syne_staking_amount })?,
-                            funds: vec![],
-                        }),
-                    );
-            } else {
-                return Err(ContractError::InvalidDistributor {});
+                    .add_attribute("Send", amount.to_string())
+                    .add_attribute("To", address.to_string())
+                    .add_message(transfer_msg(cfg.token.as_str(), &address, amount)?);
             }
         },
         RewardAction::Unstake { address, amount } => {
-            let pending_rewards = remove_stake(storage, address.clone(), amount)?;
-            let mut msgs = vec![
-                CosmosMsg::Wasm(WasmMsg::Execute {
-                    //sending reward to user
-                    contract_addr: cfg.token.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: address.to_string(), amount })?,
-                    funds: vec![],
-                }),
-            ];
+            res = pay_syne(storage, env, &address, &address, res)?;
+            res = pay_streams(storage, env, &address, &address, res)?;
+            let old_stake = STAKE.may_load(storage, &address)?.unwrap_or_default().stake;
+            let pending_rewards = remove_stake(storage, env, address.clone(), amount)?;
+            // a full exit leaves no stake to compound into, so its rewards are always sent
+            let left_stake = STAKE.load(storage, &address)?.stake;
+            let paid = if left_stake.is_zero() {
+                Response::new()
+            } else {
+                pay_rewards(storage, env, cfg, &address, pending_rewards, Response::new())?
+            };
+            let new_stake = STAKE.load(storage, &address)?.stake;
             res = res
                 .add_attribute("Unstake", amount.to_string())
-                .add_attribute("To", address.to_string());
+                .add_attribute("To", address.to_string())
+                .add_submessages(member_changed_msgs(storage, &address, old_stake, new_stake)?);
+
+            // the stake is already gone, so unbonding tokens earn nothing until claimed
+            if cfg.unbonding_period > 0 {
+                let release_at = Expiration::AtTime(env.block.time.plus_seconds(cfg.unbonding_period));
+                CLAIMS.create_claim(storage, &address, amount, release_at)?;
+                res = res.add_attribute("release_at", release_at.to_string());
+            } else {
+                res = res.add_message(transfer_msg(cfg.token.as_str(), &address, amount)?);
+            }
 
             if pending_rewards.gt(&Uint128::zero()) {
-                msgs.push(
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.token.to_string(),
-                        msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: address.to_string(), amount: pending_rewards })?,
-                        funds: vec![],
-                    }),
-                );
                 res = res
                     .add_attribute("Reward", pending_rewards.to_string())
-                    .add_attribute("To", address.to_string())
+                    .add_attribute("To", address.to_string());
+                res = if left_stake.is_zero() {
+                    res.add_message(transfer_msg(cfg.token.as_str(), &address, pending_rewards)?)
+                } else {
+                    res.add_attributes(paid.attributes).add_submessages(paid.messages)
+                };
             }
-            res = res.add_messages(msgs);
+        },
+        RewardAction::Compound {} => {
+            let start = COMPOUND_CURSOR.may_load(storage)?;
+            let batch = AUTO_COMPOUND
+                .keys(storage, start.as_ref().map(Bound::exclusive), None, Order::Ascending)
+                .take(COMPOUND_BATCH)
+                .collect::<StdResult<Vec<_>>>()?;
+            // a short batch reached the end, the next call starts over
+            match batch.last() {
+                Some(last) if batch.len() == COMPOUND_BATCH => COMPOUND_CURSOR.save(storage, last)?,
+                _ => COMPOUND_CURSOR.remove(storage),
+            }
+
+            res = res
+                .add_attribute("Action", "compound")
+                .add_attribute("Stakers", batch.len().to_string());
+            for address in batch {
+                res = pay_syne(storage, env, &address, &address, res)?;
+                res = pay_streams(storage, env, &address, &address, res)?;
+                res = compound(storage, env, &address, res)?;
+            }
+        },
+    }
+    Ok(res)
+}
+
+/// Sends the bToken `rewards` to `address`, or adds them to its stake when it auto-compounds
+fn pay_rewards(
+    storage: &mut dyn Storage,
+    env: &Env,
+    cfg: &Config,
+    address: &Addr,
+    rewards: Uint128,
+    res: Response,
+) -> Result<Response, ContractError> {
+    if rewards.is_zero() {
+        return Ok(res);
+    }
+    if AUTO_COMPOUND.has(storage, address) {
+        add_stake(storage, env, address.clone(), rewards)?;
+        return Ok(res.add_attribute("Compound", rewards.to_string()));
+    }
+    Ok(res.add_message(transfer_msg(cfg.token.as_str(), address, rewards)?))
+}
+
+/// Stakers compounded by one `Compound {}` call
+const COMPOUND_BATCH: usize = 30;
+
+/// Adds the pending bToken rewards of `address` to its stake. SYNE and the reward streams
+/// must already be paid, as they are accrued on the stake before the change.
+fn compound(
+    storage: &mut dyn Storage,
+    env: &Env,
+    address: &Addr,
+    res: Response,
+) -> Result<Response, ContractError> {
+    let old_stake = STAKE.may_load(storage, address)?.unwrap_or_default().stake;
+    let reward_amount = update_rewards(storage, env, address.clone())?;
+    if reward_amount.is_zero() {
+        return Ok(res);
+    }
+    // the rewards already sit in the vault, so they only move from pending to staked
+    add_stake(storage, env, address.clone(), reward_amount)?;
+    let new_stake = STAKE.load(storage, address)?.stake;
+    Ok(res
+        .add_attribute("Compound", reward_amount.to_string())
+        .add_attribute("To", address.to_string())
+        .add_submessages(member_changed_msgs(storage, address, old_stake, new_stake)?))
+}
 
+/// Emits SYNE into the per-share index for the time passed since the last update. Nothing is
+/// emitted while the vault is empty, so the first staker does not collect the backlog.
+fn accrue_syne(
+    syne_config: &SyneDistributionConfig,
+    mut current: CurrentSyneDistribution,
+    staked: Uint128,
+    now: u64,
+) -> Result<CurrentSyneDistribution, ContractError> {
+    let elapsed = now.saturating_sub(current.last_distributed_time);
+    current.last_distributed_time = current.last_distributed_time.max(now);
+    if staked.is_zero() {
+        return Ok(current);
+    }
+
+    let emitted = Uint128::from(syne_config.distribution_per_day)
+        .multiply_ratio(elapsed, SECONDS_PER_DAY)
+        .min(current.pending);
+    current.pending = current.pending.checked_sub(emitted)?;
+    current.total_distributed = current.total_distributed.checked_add(emitted)?;
+    current.power = current.power.checked_add(per_share(emitted, staked)?).map_err(ContractError::RewardOverflow)?;
+    Ok(current)
+}
+
+/// Brings the SYNE index up to `env`, if SYNE is distributed at all
+fn update_syne_distribution(
+    storage: &mut dyn Storage,
+    env: &Env,
+) -> Result<Option<(SyneDistributionConfig, CurrentSyneDistribution)>, ContractError> {
+    let Some(syne_config) = SYNE_DISTRIBUTION_CONFIG.may_load(storage)? else {
+        return Ok(None);
+    };
+    let staked = TOTAL_STAKED.load(storage)?.bonded;
+    let current = accrue_syne(&syne_config, CURRENT_SYNE_DISTRIBUTION.load(storage)?, staked, env.block.time.seconds())?;
+    CURRENT_SYNE_DISTRIBUTION.save(storage, &current)?;
+    Ok(Some((syne_config, current)))
+}
+
+/// Settles the SYNE `address` earned so far and adds its payout to `res`.
+/// Has to run before the stake of `address` changes.
+fn pay_syne(
+    storage: &mut dyn Storage,
+    env: &Env,
+    address: &Addr,
+    receiver: &Addr,
+    res: Response,
+) -> Result<Response, ContractError> {
+    let Some((syne_config, current)) = update_syne_distribution(storage, env)? else {
+        return Ok(res);
+    };
+
+    let stake = bonded_stake(storage, address)?;
+    let power_diff = SYNE_POWER_DIFF.may_load(storage, address)?.unwrap_or_default();
+    SYNE_POWER_DIFF.save(storage, address, &current.power)?;
+
+    let syne_amount = earned_rewards(current.power, power_diff, stake)?;
+    if syne_amount.is_zero() {
+        return Ok(res);
+    }
+    Ok(res
+        .add_attribute("Syne", syne_amount.to_string())
+        .add_attribute("To", receiver.to_string())
+        .add_message(transfer_msg(&syne_config.syne_addr, receiver, syne_amount)?))
+}
+
+pub fn execute_fund_syne(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let syne_config = SYNE_DISTRIBUTION_CONFIG.may_load(deps.storage)?.ok_or(ContractError::SyneNotConfigured {})?;
+    ensure_eq!(info.sender.as_str(), syne_config.syne_addr, ContractError::InvalidAsset {});
+    ADMIN.assert_admin(deps.as_ref(), &deps.api.addr_validate(&wrapper.sender)?)?;
+
+    let (_, mut current) = update_syne_distribution(deps.storage, &env)?.ok_or(ContractError::SyneNotConfigured {})?;
+    current.pending = current.pending.checked_add(wrapper.amount)?;
+    CURRENT_SYNE_DISTRIBUTION.save(deps.storage, &current)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_syne")
+        .add_attribute("amount", wrapper.amount)
+        .add_attribute("pending", current.pending))
+}
+
+pub fn execute_update_syne_distribution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    syne_addr: Option<String>,
+    distribution_per_day: Option<u64>,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    // the emissions so far still run at the old rate
+    let (previous, current) = match update_syne_distribution(deps.storage, &env)? {
+        Some((syne_config, current)) => (Some(syne_config), current),
+        None => (None, CurrentSyneDistribution {
+            last_distributed_time: env.block.time.seconds(),
+            ..CurrentSyneDistribution::default()
+        }),
+    };
+
+    let syne_addr = match (syne_addr, &previous) {
+        (Some(syne_addr), Some(syne_config)) if syne_addr != syne_config.syne_addr => {
+            // stakers would be owed SYNE in a token the vault no longer pays out
+            ensure_eq!((current.pending.is_zero() && current.total_distributed.is_zero()), true, ContractError::SyneTokenLocked {});
+            deps.api.addr_validate(&syne_addr)?.to_string()
+        }
+        (Some(syne_addr), _) => deps.api.addr_validate(&syne_addr)?.to_string(),
+        (None, Some(syne_config)) => syne_config.syne_addr.clone(),
+        (None, None) => return Err(ContractError::SyneNotConfigured {}),
+    };
+    let distribution_per_day = distribution_per_day
+        .or_else(|| previous.map(|syne_config| syne_config.distribution_per_day))
+        .unwrap_or_default();
+
+    SYNE_DISTRIBUTION_CONFIG.save(deps.storage, &SyneDistributionConfig { syne_addr: syne_addr.clone(), distribution_per_day })?;
+    CURRENT_SYNE_DISTRIBUTION.save(deps.storage, &current)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_syne_distribution")
+        .add_attribute("syne_addr", syne_addr)
+        .add_attribute("distribution_per_day", distribution_per_day.to_string()))
+}
+
+fn emit_stream(mut stream: RewardStream, emitted: Uint128, staked: Uint128) -> Result<RewardStream, ContractError> {
+    stream.remaining = stream.remaining.checked_sub(emitted)?;
+    stream.total_distributed = stream.total_distributed.checked_add(emitted)?;
+    stream.power = stream.power.checked_add(per_share(emitted, staked)?).map_err(ContractError::RewardOverflow)?;
+    Ok(stream)
+}
+
+/// Emits the stream evenly over the time left until its end. Time the vault spends empty is
+/// skipped, so what is left is spread over the rest of the stream, or paid to the next stakers
+/// once it ended.
+fn accrue_stream(mut stream: RewardStream, staked: Uint128, now: u64) -> Result<RewardStream, ContractError> {
+    if now <= stream.last_update {
+        return Ok(stream);
+    }
+    if staked.is_zero() {
+        stream.last_update = now;
+        return Ok(stream);
+    }
+
+    let emitted = if now >= stream.end {
+        stream.remaining
+    } else {
+        stream.remaining.multiply_ratio(now - stream.last_update, stream.end - stream.last_update)
+    };
+    stream.last_update = now;
+    emit_stream(stream, emitted, staked)
+}
+
+/// Settles the rewards `address` earned in every reward stream and adds their payouts to `res`.
+/// Has to run before the stake of `address` changes.
+fn pay_streams(
+    storage: &mut dyn Storage,
+    env: &Env,
+    address: &Addr,
+    receiver: &Addr,
+    mut res: Response,
+) -> Result<Response, ContractError> {
+    let staked = TOTAL_STAKED.load(storage)?.bonded;
+    let stake = bonded_stake(storage, address)?;
+
+    let streams = REWARD_STREAMS
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (asset, stream) in streams {
+        let stream = accrue_stream(stream, staked, env.block.time.seconds())?;
+        REWARD_STREAMS.save(storage, &asset, &stream)?;
+
+        let power_diff = STREAM_POWER_DIFF.may_load(storage, (address, &asset))?.unwrap_or_default();
+        STREAM_POWER_DIFF.save(storage, (address, &asset), &stream.power)?;
+
+        let reward = asset.with_balance(earned_rewards(stream.power, power_diff, stake)?);
+        if !reward.amount.is_zero() {
+            res = res
+                .add_attribute("Reward", reward.to_string())
+                .add_attribute("To", receiver.to_string())
+                .add_message(reward.into_msg(receiver)?);
         }
     }
-    reward_action = RewardAction::NoAction {};
-    REWARD_ACTION.save(storage, &reward_action)?;
     Ok(res)
 }
 
+pub fn execute_add_reward_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: AssetInfo,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let asset = asset.validate(deps.api)?;
+
+    // the staked token is rewarded through `TokenInfo.power`
+    ensure_eq!((asset != AssetInfoValidated::Token(cfg.token)), true, ContractError::InvalidAsset {});
+    if REWARD_STREAMS.has(deps.storage, &asset) {
+        return Err(ContractError::DistributionAlreadyExists(asset));
+    }
+    let streams = REWARD_STREAMS.keys(deps.storage, None, None, Order::Ascending).count();
+    ensure_eq!((streams < MAX_REWARD_STREAMS as usize), true, ContractError::TooManyDistributions(MAX_REWARD_STREAMS));
+
+    let now = env.block.time.seconds();
+    REWARD_STREAMS.save(deps.storage, &asset, &RewardStream {
+        power: Decimal256::zero(),
+        remaining: Uint128::zero(),
+        total_distributed: Uint128::zero(),
+        last_update: now,
+        end: now,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_reward_stream")
+        .add_attribute("asset", asset.to_string()))
+}
+
+pub fn execute_fund_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure_eq!((info.funds.len() == 1), true, ContractError::InvalidRewards {});
+    let coin = &info.funds[0];
+
+    fund_reward_stream(deps.storage, &env, AssetInfoValidated::Native(coin.denom.clone()), coin.amount, duration)
+}
+
+/// Adds `amount` to the reward stream in `asset`. It is spread until `duration` seconds from now,
+/// or the current end of the stream if that is later, and distributed at once without a duration.
+pub fn fund_reward_stream(
+    storage: &mut dyn Storage,
+    env: &Env,
+    asset: AssetInfoValidated,
+    amount: Uint128,
+    duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure_eq!((!amount.is_zero()), true, ContractError::InvalidRewards {});
+    let stream = REWARD_STREAMS.may_load(storage, &asset)?.ok_or_else(|| ContractError::NoRewardStream(asset.clone()))?;
+
+    let staked = TOTAL_STAKED.load(storage)?.bonded;
+    let now = env.block.time.seconds();
+    let mut stream = accrue_stream(stream, staked, now)?;
+    stream.remaining = stream.remaining.checked_add(amount)?;
+    match duration {
+        Some(0) => return Err(ContractError::ZeroRewardDuration {}),
+        Some(duration) => stream.end = stream.end.max(now + duration),
+        None => {
+            ensure_eq!((!staked.is_zero()), true, ContractError::NoMembersToDistributeTo {});
+            stream = emit_stream(stream, amount, staked)?;
+        }
+    }
+    REWARD_STREAMS.save(storage, &asset, &stream)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fund_rewards")
+        .add_attribute("asset", asset.to_string())
+        .add_attribute("amount", amount)
+        .add_attribute("end", stream.end.to_string()))
+}
+
+pub fn execute_distribute_user_rewards(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Claim)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    apply_reward_action(deps.storage, &env, &cfg, RewardAction::Reward { address, receiver: None, swap: None })
+}
+
+pub fn execute_distribute_rewards(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    address: Option<Addr>,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let storage = deps.storage;
+
+    update_total_rewards(storage, &env, wrapper.amount)?;
+
+    let mut res = Response::new();
+    // the converter pays out the rewards of the user who triggered the harvest by converting.
+    // While claims are paused they only accrue.
+    let paused = PAUSABLE.is_paused(storage, PausableOperation::Claim)?;
+    if let Some(address) = address.filter(|_| !paused) {
+        res = apply_reward_action(storage, &env, &cfg, RewardAction::Reward { address, receiver: None, swap: None })?;
+    }
+    Ok(res
+        .add_attribute("Action", "distribute_rewards")
+        .add_attribute("Amount", wrapper.amount))
+}
+
 pub fn update_total_rewards(
     storage: &mut dyn Storage,
+    env: &Env,
     reward: Uint128,
 ) -> Result<(), ContractError> {
     let mut total_stake = TOTAL_STAKED.load(storage)?;
     let cfg = CONFIG.load(storage)?;
-    let treasury_rewards = Decimal::from_atomics(reward, 0).unwrap().checked_mul(cfg.treasury_fee).unwrap().to_uint_floor();
-    let syne_staking_rewards = Decimal::from_atomics(reward, 0).unwrap().checked_mul(cfg.syne_staking_fee).unwrap().to_uint_floor();
-    total_stake.pending_treasury_rewards = total_stake.pending_treasury_rewards.checked_add(
-        treasury_rewards
-    ).unwrap();
-    total_stake.pending_syne_staking_rewards = total_stake.pending_syne_staking_rewards.checked_add(syne_staking_rewards).unwrap();
-    if total_stake.staked.gt(&Uint128::zero()) {
-        total_stake.power = total_stake.power.checked_add(
-            Decimal::from_atomics(
-                reward.checked_sub(treasury_rewards).unwrap().checked_sub(syne_staking_rewards).unwrap(), 0
-            ).unwrap().checked_div(Decimal::from_atomics(total_stake.staked, 0).unwrap()).unwrap()
-        ).unwrap();
-    }
-
-    TOTAL_STAKED.save(storage,&total_stake)?;
+    let mut fees = Uint128::zero();
+    for recipient in &cfg.fee_recipients {
+        let fee = fee_of(reward, recipient.share)?;
+        if fee.is_zero() {
+            continue;
+        }
+        PENDING_FEES.update(storage, &recipient.address, |pending| -> StdResult<_> {
+            Ok(pending.unwrap_or_default().checked_add(fee)?)
+        })?;
+        fees = fees.checked_add(fee)?;
+    }
+    let distributed = reward.checked_sub(fees)?;
+    if total_stake.bonded.gt(&Uint128::zero()) {
+        // floored, what the division leaves over stays in the vault
+        total_stake.power = total_stake.power
+            .checked_add(per_share(distributed, total_stake.bonded)?)
+            .map_err(ContractError::RewardOverflow)?;
+    }
+
+    TOTAL_STAKED.save(storage, &total_stake, env.block.height)?;
+
+    let mut total_distributed = Uint128::zero();
+    update_stats(storage, |stats| {
+        stats.fees = stats.fees.checked_add(fees)?;
+        if !total_stake.bonded.is_zero() {
+            stats.distributed = stats.distributed.checked_add(distributed)?;
+        }
+        total_distributed = stats.distributed;
+        Ok(())
+    })?;
+
+    let point = RewardPoint { distributed: total_distributed, power: total_stake.power };
+    record_reward_point(storage, env.block.time.seconds(), point)?;
 
     Ok(())
 }
 
+/// Adds a point to each reward history at most once per step, the first one of a step is kept
+fn record_reward_point(storage: &mut dyn Storage, now: u64, point: RewardPoint) -> StdResult<()> {
+    let mut recorded = REWARDS_RECORDED.may_load(storage)?.unwrap_or_default();
+    if recorded.hours + HOUR <= now {
+        DAY_REWARDS.save(storage, now, point.clone())?;
+        recorded.hours = now;
+    }
+    if recorded.half_days + HALF_DAY <= now {
+        WEEK_REWARDS.save(storage, now, point.clone())?;
+        recorded.half_days = now;
+    }
+    if recorded.days + DAY <= now {
+        MONTH_REWARDS.save(storage, now, point)?;
+        recorded.days = now;
+    }
+    REWARDS_RECORDED.save(storage, &recorded)
+}
+
+fn update_stats(
+    storage: &mut dyn Storage,
+    action: impl FnOnce(&mut VaultStats) -> StdResult<()>,
+) -> StdResult<()> {
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    action(&mut stats)?;
+    STATS.save(storage, &stats)
+}
+
+/// Keeps the staker count in step with a stake going from `old` to `new`
+fn count_staker(storage: &mut dyn Storage, old: Uint128, new: Uint128) -> StdResult<()> {
+    match (old.is_zero(), new.is_zero()) {
+        (true, false) => update_stats(storage, |stats| {
+            stats.stakers += 1;
+            Ok(())
+        }),
+        (false, true) => update_stats(storage, |stats| {
+            stats.stakers = stats.stakers.saturating_sub(1);
+            Ok(())
+        }),
+        _ => Ok(()),
+    }
+}
+
 pub fn update_rewards(
     storage: &mut dyn Storage,
+    env: &Env,
     address: Addr,
 ) -> Result<Uint128, ContractError> {
     let total_stake = TOTAL_STAKED.load(storage)?;
     let mut pending_rewards = Uint128::zero();
+    let min_bond = CONFIG.load(storage)?.min_bond;
     STAKE.update(
         storage,
         &address,
-        |staking_info| -> StdResult<_> {
+        env.block.height,
+        |staking_info| -> Result<_, ContractError> {
             let mut staking_info = staking_info.unwrap_or_default();
-            pending_rewards = (total_stake.power
-                .checked_sub(staking_info.power_diff)
-                .unwrap_or_default()
-            ).checked_mul(Decimal::from_atomics(staking_info.stake, 0).unwrap()).unwrap_or_default()
-            .to_uint_floor();
+            pending_rewards = earned_rewards(total_stake.power, staking_info.power_diff, bonded(staking_info.stake, min_bond))?;
             staking_info.power_diff = total_stake.power;
             Ok(staking_info)
         },
@@ -614,169 +1167,174 @@ pub fn update_rewards(
 
 pub fn add_stake(
     storage: &mut dyn Storage,
+    env: &Env,
     address: Addr,
     amount: Uint128,
 ) -> Result<Uint128, ContractError> {
     let mut total_stake = TOTAL_STAKED.load(storage)?;
     let mut pending_rewards = Uint128::zero();
+    let min_bond = CONFIG.load(storage)?.min_bond;
+    let mut old_stake = Uint128::zero();
+    let mut new_stake = Uint128::zero();
     STAKE.update(
         storage,
         &address,
-        |staking_info| -> StdResult<_> {
+        env.block.height,
+        |staking_info| -> Result<_, ContractError> {
             let mut staking_info = staking_info.unwrap_or_default();
-            pending_rewards = (total_stake.power
-                .checked_sub(staking_info.power_diff)
-                .unwrap_or_default()
-            ).checked_mul(Decimal::from_atomics(staking_info.stake, 0).unwrap()).unwrap_or_default()
-            .to_uint_floor();
-            staking_info.stake = staking_info.stake.checked_add(amount).unwrap_or_default();
+            pending_rewards = earned_rewards(total_stake.power, staking_info.power_diff, bonded(staking_info.stake, min_bond))?;
+            old_stake = staking_info.stake;
+            staking_info.stake = staking_info.stake.checked_add(amount).map_err(ContractError::RewardOverflow)?;
+            new_stake = staking_info.stake;
             staking_info.power_diff = total_stake.power;
             Ok(staking_info)
         }
     )?;
-    total_stake.staked = total_stake.staked.checked_add(amount).unwrap_or_default();
-    TOTAL_STAKED.save(storage, &total_stake)?;
+    total_stake.staked = total_stake.staked.checked_add(amount).map_err(ContractError::RewardOverflow)?;
+    total_stake.bonded = total_stake.bonded.checked_sub(bonded(old_stake, min_bond))?
+        .checked_add(bonded(new_stake, min_bond))
+        .map_err(ContractError::RewardOverflow)?;
+    TOTAL_STAKED.save(storage, &total_stake, env.block.height)?;
+    count_staker(storage, old_stake, new_stake)?;
     Ok(pending_rewards)
 }
 
 pub fn remove_stake(
     storage: &mut dyn Storage,
+    env: &Env,
     address: Addr,
     amount: Uint128,
 ) -> Result<Uint128, ContractError> {
     let mut total_stake = TOTAL_STAKED.load(storage)?;
     let mut pending_rewards = Uint128::zero();
+    let min_bond = CONFIG.load(storage)?.min_bond;
+    let mut old_stake = Uint128::zero();
+    let mut new_stake = Uint128::zero();
     STAKE.update(
         storage,
         &address,
-        |staking_info| -> StdResult<_> {
+        env.block.height,
+        |staking_info| -> Result<_, ContractError> {
             let mut staking_info = staking_info.unwrap_or_default();
-            pending_rewards = (total_stake.power
-                .checked_sub(staking_info.power_diff)
-                .unwrap_or_default()
-            ).checked_mul(Decimal::from_atomics(staking_info.stake, 0).unwrap()).unwrap_or_default()
-            .to_uint_floor();
-            staking_info.stake = staking_info.stake.checked_sub(amount).unwrap_or_default();
+            pending_rewards = earned_rewards(total_stake.power, staking_info.power_diff, bonded(staking_info.stake, min_bond))?;
+            old_stake = staking_info.stake;
+            staking_info.stake = staking_info.stake.checked_sub(amount)
+                .map_err(|_| ContractError::StakeUnderflow { stake: staking_info.stake, amount })?;
+            new_stake = staking_info.stake;
             staking_info.power_diff = total_stake.power;
             Ok(staking_info)
         }
     )?;
-    total_stake.staked = total_stake.staked.checked_sub(amount).unwrap_or_default();
-    TOTAL_STAKED.save(storage, &total_stake)?;
+    total_stake.staked = total_stake.staked.checked_sub(amount)
+        .map_err(|_| ContractError::StakeUnderflow { stake: total_stake.staked, amount })?;
+    total_stake.bonded = total_stake.bonded.checked_sub(bonded(old_stake, min_bond))?
+        .checked_add(bonded(new_stake, min_bond))
+        .map_err(ContractError::RewardOverflow)?;
+    TOTAL_STAKED.save(storage, &total_stake, env.block.height)?;
+    count_staker(storage, old_stake, new_stake)?;
     Ok(pending_rewards)
 }
 
-pub fn update_treasury_amounts(
-    storage: &mut dyn Storage,
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    amount: Option<Uint128>,
-) -> Result<Uint128, ContractError> {
-    let cfg = CONFIG.load(storage)?;
-    ensure_eq!(info.sender, cfg.bloop_converter_and_staker.clone(), ContractError::Unauthorized {});
-    
-    let mut total_staked = TOTAL_STAKED.load(storage)?;
-    let amount = amount.unwrap_or(total_staked.pending_treasury_rewards);
-    ensure_eq!((amount.le(&total_staked.pending_treasury_rewards)), true, ContractError::InvalidAmount {});
+    amount: Uint128
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Withdraw)?;
 
-    total_staked.pending_treasury_rewards = total_staked.pending_treasury_rewards.checked_sub(amount).unwrap();
-    TOTAL_STAKED.save(storage, &total_staked)?;
+    let stake = STAKE.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+    ensure_eq!((amount.gt(&Uint128::zero())), true, ContractError::InvalidAmount {});
+    ensure_eq!((amount.le(&stake.stake)), true, ContractError::InvalidAmount {});
 
-    Ok(amount)
+    // a rest below the minimum bond would earn nothing, so it leaves as well
+    let cfg = CONFIG.load(deps.storage)?;
+    let amount = if stake.stake.checked_sub(amount)? < cfg.min_bond { stake.stake } else { amount };
+
+    harvest_then(deps, &env, RewardAction::Unstake { address: info.sender, amount })
 }
 
-pub fn update_syne_staking_amounts(
-    storage: &mut dyn Storage,
-    info: MessageInfo,
-    amount: Option<Uint128>,
-) -> Result<Uint128, ContractError> {
-    let cfg = CONFIG.load(storage)?;
-    ensure_eq!(info.sender, cfg.bloop_converter_and_staker.clone(), ContractError::Unauthorized {});
-    
-    let mut total_staked = TOTAL_STAKED.load(storage)?;
-    let amount = amount.unwrap_or(total_staked.pending_syne_staking_rewards);
-    ensure_eq!((amount.le(&total_staked.pending_syne_staking_rewards)), true, ContractError::InvalidAmount {});
+/// Moves `amount` of the stake of `owner` to `recipient` once the rewards are harvested
+fn execute_transfer_shares(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    recipient: Addr,
+    amount: Uint128,
+    receive: Option<Cw20ReceiveMsg>,
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Withdraw)?;
 
-    total_staked.pending_syne_staking_rewards = total_staked.pending_syne_staking_rewards.checked_sub(amount).unwrap();
-    TOTAL_STAKED.save(storage, &total_staked)?;
+    let stake = STAKE.may_load(deps.storage, &owner)?.unwrap_or_default().stake;
+    ensure_eq!((amount.gt(&Uint128::zero())), true, ContractError::InvalidAmount {});
+    ensure_eq!((amount.le(&stake)), true, ContractError::InvalidAmount {});
+
+    // unlike an unstake, a transfer cannot take the dust along, so it leaves none behind
+    let cfg = CONFIG.load(deps.storage)?;
+    let rest = stake.checked_sub(amount)?;
+    let received = STAKE.may_load(deps.storage, &recipient)?.unwrap_or_default().stake.checked_add(amount)?;
+    ensure_eq!((rest.is_zero() || rest >= cfg.min_bond), true, ContractError::BelowMinBond(cfg.min_bond));
+    ensure_eq!((received >= cfg.min_bond), true, ContractError::BelowMinBond(cfg.min_bond));
 
-    Ok(amount)
+    harvest_then(deps, &env, RewardAction::Transfer { owner, recipient, amount, receive })
 }
 
-pub fn execute_unstake(
+pub fn execute_claim(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    amount: Uint128
 ) -> Result<Response, ContractError> {
-    let stake = STAKE.may_load(deps.storage, &info.sender)?.unwrap_or_default();
-    ensure_eq!((amount.gt(&Uint128::zero())), true, ContractError::InvalidAmount {});
-    ensure_eq!((amount.le(&stake.stake)), true, ContractError::InvalidAmount {});
-
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Claim)?;
     let cfg = CONFIG.load(deps.storage)?;
-    let storage = deps.storage;
-    
-    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking, cfg.bloop_converter_and_staker.clone())?;
-    let withdrawable_amount = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
-    
-    if withdrawable_amount.gt(&Uint128::zero()) {
-        let mut reward_action = REWARD_ACTION.load(storage).unwrap_or(RewardAction::NoAction {});
-        match reward_action {
-            RewardAction::NoAction {} => {
-                reward_action = RewardAction::Unstake { address: info.sender, amount };
-            
-                let messages = vec![
-                    CosmosMsg::Wasm(WasmMsg::Execute {
-                        //sending reward to user
-                        contract_addr: cfg.bloop_converter_and_staker.clone().to_string(),
-                        msg: to_binary(&WithdrawMsg::WithdrawRewards {})?,
-                        funds: vec![],
-                    }),
-                ];
-            
-                REWARD_ACTION.save(storage, &reward_action)?;
-            
-                Ok(Response::new().add_messages(messages))
-            },
-            _ => {Err(ContractError::InvalidAction {})}
-        }
+
+    let amount = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
+    ensure_eq!((amount.gt(&Uint128::zero())), true, ContractError::NothingToClaim {});
+
+    Ok(Response::new()
+        .add_attribute("action", "claim")
+        .add_attribute("to", info.sender.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_message(transfer_msg(cfg.token.as_str(), &info.sender, amount)?))
+}
+
+pub fn execute_set_auto_compound(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    if enabled {
+        AUTO_COMPOUND.save(deps.storage, &info.sender, &Empty {})?;
     } else {
-        let pending_rewards = remove_stake(storage, info.sender.clone(), amount)?;
-        let mut msgs = vec![
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                //sending reward to user
-                contract_addr: cfg.token.to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: info.sender.to_string(), amount })?,
-                funds: vec![],
-            }),
-        ];
-        let mut res = Response::new()
-            .add_attribute("Unstake", amount.to_string())
-            .add_attribute("To", info.sender.to_string());
-
-        if pending_rewards.gt(&Uint128::zero()) {
-            msgs.push(
-                CosmosMsg::Wasm(WasmMsg::Execute {
-                    //sending reward to user
-                    contract_addr: cfg.token.to_string(),
-                    msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: info.sender.to_string(), amount: pending_rewards })?,
-                    funds: vec![],
-                }),
-            );
-            res = res
-                .add_attribute("Reward", pending_rewards.to_string())
-                .add_attribute("To", info.sender.to_string())
-        }
-        res = res.add_messages(msgs);
-        Ok(res)
+        AUTO_COMPOUND.remove(deps.storage, &info.sender);
     }
 
+    Ok(Response::new()
+        .add_attribute("action", "set_auto_compound")
+        .add_attribute("address", info.sender.to_string())
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+pub fn execute_compound(
+    deps: DepsMut,
+    env: Env,
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Deposit)?;
+
+    harvest_then(deps, &env, RewardAction::Compound {})
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Admin {} => {
             to_binary(&query_admin(deps)?)
         },
+        QueryMsg::IsPaused {} => {
+            to_binary(&PAUSABLE.query_is_paused(deps)?)
+        },
+        QueryMsg::OwnershipProposal {} => {
+            to_binary(&OWNERSHIP_PROPOSAL.query_ownership_proposal(deps)?)
+        },
         QueryMsg::Config {} => {
             to_binary(&query_config(deps)?)
         },
@@ -789,14 +1347,59 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Reward { address } => {
             to_binary(&query_reward(deps, address)?)
         },
+        QueryMsg::DelegatedWithdrawal { owner } => {
+            to_binary(&query_delegated_withdrawal(deps, owner)?)
+        },
         QueryMsg::TotalPendingReward {} => {
             to_binary(&staker_pending_reward(deps)?)
         },
-        QueryMsg::TreasuryReward {} => {
-            to_binary(&query_treasury_reward(deps)?)
+        QueryMsg::PendingFees {} => {
+            to_binary(&query_pending_fees(deps)?)
+        },
+        QueryMsg::SyneReward { address } => {
+            to_binary(&query_syne_reward(deps, env, address)?)
+        },
+        QueryMsg::SyneDistribution {} => {
+            to_binary(&query_syne_distribution(deps, env)?)
+        },
+        QueryMsg::RewardStreams {} => {
+            to_binary(&query_reward_streams(deps, env)?)
+        },
+        QueryMsg::StreamRewards { address } => {
+            to_binary(&query_stream_rewards(deps, env, address)?)
         },
-        QueryMsg::SyneStakingReward {} => {
-            to_binary(&query_syne_staking_reward(deps)?)
+        QueryMsg::Claims { address } => {
+            to_binary(&CLAIMS.query_claims(deps, &deps.api.addr_validate(&address)?)?)
+        },
+        QueryMsg::Hooks {} => {
+            to_binary(&HOOKS.query_hooks(deps)?)
+        },
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            to_binary(&query_voting_power_at_height(deps, env, address, height)?)
+        },
+        QueryMsg::TotalPowerAtHeight { height } => {
+            to_binary(&query_total_power_at_height(deps, env, height)?)
+        },
+        QueryMsg::Info {} => {
+            to_binary(&query_info(deps)?)
+        },
+        QueryMsg::ListStakers { start_after, limit } => {
+            to_binary(&query_list_stakers(deps, start_after, limit)?)
+        },
+        QueryMsg::Stats {} => {
+            to_binary(&query_stats(deps)?)
+        }
+        QueryMsg::RewardRate { window } => {
+            to_binary(&query_reward_rate(deps, env, window)?)
+        }
+        QueryMsg::Balance { address } => {
+            to_binary(&query_balance(deps, address)?)
+        }
+        QueryMsg::TokenInfo {} => {
+            to_binary(&query_token_info(deps)?)
+        }
+        QueryMsg::Allowance { owner, spender } => {
+            to_binary(&query_allowance(deps, owner, spender)?)
         }
     }
 }
@@ -804,41 +1407,54 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 pub fn query_admin(
     deps: Deps
 ) -> StdResult<AdminResponse> {
-    let cfg = CONFIG.load(deps.storage).unwrap();
-    Ok(AdminResponse { admin: cfg.admin })
+    Ok(AdminResponse { admin: load_admin(deps)? })
+}
+
+/// The vault is always instantiated with an admin and the admin can only be handed over
+fn load_admin(deps: Deps) -> StdResult<Addr> {
+    ADMIN.get(deps)?.ok_or_else(|| StdError::not_found("admin"))
 }
 
 pub fn query_config(
     deps: Deps
 ) -> StdResult<ConfigResponse> {
-    let cfg = CONFIG.load(deps.storage).unwrap();
+    let cfg = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         token: cfg.token,
         bloop_converter_and_staker: cfg.bloop_converter_and_staker.clone(),
         loop_protocol_staking: cfg.loop_protocol_staking,
         min_bond: cfg.min_bond,
-        treasury_wallet: cfg.treasury_wallet,
-        treasury_withdrawer: cfg.treasury_withdrawer,
-        syne_staking_reward_distributor: cfg.syne_staking_reward_distributor,
-        treasury_fee: cfg.treasury_fee,
-        syne_staking_fee: cfg.syne_staking_fee,
+        fee_recipients: cfg.fee_recipients,
+        total_fee_cap: cfg.total_fee_cap,
         duration: cfg.duration,
+        unbonding_period: cfg.unbonding_period,
+        swap_router: cfg.swap_router,
     })
 }
 
 pub fn query_total_staked(
     deps: Deps
 ) -> StdResult<TotalStakedResponse> {
-    let total_staked = TOTAL_STAKED.load(deps.storage).unwrap();
-    Ok(TotalStakedResponse { total_staked: total_staked.staked, power: total_staked.power, pending_treasury_rewards: total_staked.pending_treasury_rewards })
+    let total_staked = TOTAL_STAKED.load(deps.storage)?;
+    Ok(TotalStakedResponse { total_staked: total_staked.staked, power: total_staked.power })
 }
 
 pub fn query_staked(
     deps: Deps,
     address: String
 ) -> StdResult<StakedResponse> {
-    let staked = STAKE.may_load(deps.storage, &deps.api.addr_validate(&address).unwrap())?.unwrap_or_default();
-    Ok(StakedResponse { stake: staked.stake, power_diff: staked.power_diff })
+    let address = deps.api.addr_validate(&address)?;
+    let staked = STAKE.may_load(deps.storage, &address)?.unwrap_or_default();
+    Ok(StakedResponse {
+        stake: staked.stake,
+        power_diff: staked.power_diff,
+        auto_compound: AUTO_COMPOUND.has(deps.storage, &address),
+    })
+}
+
+pub fn query_delegated_withdrawal(deps: Deps, owner: String) -> StdResult<DelegatedWithdrawalResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    Ok(DelegatedWithdrawalResponse { delegated: WITHDRAW_DELEGATES.may_load(deps.storage, &owner)? })
 }
 
 pub fn staker_pending_reward(
@@ -846,7 +1462,7 @@ pub fn staker_pending_reward(
 ) -> StdResult<RewardResponse> {
     let cfg = CONFIG.load(deps.storage)?;
     let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking, cfg.bloop_converter_and_staker.clone())?;
-    let staker_pending_reward = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
+    let staker_pending_reward = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward)?;
     Ok(RewardResponse { rewards: staker_pending_reward })
 }
 
@@ -857,59 +1473,362 @@ pub fn query_reward(
     let cfg = CONFIG.load(deps.storage)?;
     let total_stake = TOTAL_STAKED.load(deps.storage)?;
 
-    if total_stake.staked.eq(&Uint128::zero()) {
+    if total_stake.bonded.is_zero() {
         return Ok(RewardResponse { rewards: Uint128::zero() });
     }
-    let stake = STAKE.may_load(deps.storage, &deps.api.addr_validate(&address).unwrap())?.unwrap_or_default();
+    let stake = STAKE.may_load(deps.storage, &deps.api.addr_validate(&address)?)?.unwrap_or_default();
 
-    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking, cfg.bloop_converter_and_staker.clone())?;
-    let staker_pending_reward = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
-    let rewards = (total_stake.power.checked_sub(stake.power_diff).unwrap())
-        .checked_mul(Decimal::from_atomics(stake.stake, 0).unwrap()).unwrap().to_uint_floor()
-        .checked_add(
-            Decimal::from_atomics(staker_pending_reward, 0).unwrap()
-                .checked_mul(Decimal::one().checked_sub(cfg.treasury_fee).unwrap().checked_sub(cfg.syne_staking_fee).unwrap()).unwrap()
-                .checked_mul(Decimal::from_atomics(stake.stake, 0).unwrap()).unwrap()
-                .checked_div(Decimal::from_atomics(total_stake.staked, 0).unwrap()).unwrap()
-                .to_uint_floor()
-        )?;
+    let rewards = pending_reward(&cfg, &total_stake, &stake, staker_pending_reward(deps)?.rewards)?;
     Ok(RewardResponse { rewards })
 }
 
-pub fn query_treasury_reward(
+/// Rewards of `stake` not withdrawn yet, including its share of the `undistributed` rewards
+/// still waiting in the converter
+fn pending_reward(
+    cfg: &Config,
+    total_stake: &TokenInfo,
+    stake: &StakingInfo,
+    undistributed: Uint128,
+) -> StdResult<Uint128> {
+    if total_stake.bonded.is_zero() {
+        return Ok(Uint128::zero());
+    }
+    let bonded_stake = bonded(stake.stake, cfg.min_bond);
+    let earned = earned_rewards(total_stake.power, stake.power_diff, bonded_stake).map_err(reward_error)?;
+
+    let mut distributed = undistributed;
+    for recipient in &cfg.fee_recipients {
+        distributed = distributed.checked_sub(fee_of(undistributed, recipient.share).map_err(reward_error)?)?;
+    }
+    let share = distributed
+        .checked_multiply_ratio(bonded_stake, total_stake.bonded)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    Ok(earned.checked_add(share)?)
+}
+
+/// Reports an accounting error of a query
+fn reward_error(err: ContractError) -> StdError {
+    StdError::generic_err(err.to_string())
+}
+
+// the share token, whose balances are the stakes
+const SHARE_NAME: &str = "Staked bLOOP";
+const SHARE_SYMBOL: &str = "sbLOOP";
+/// Same as the bToken
+const SHARE_DECIMALS: u8 = 6;
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn query_list_stakers(
     deps: Deps,
-) -> StdResult<RewardResponse> {
-    let storage = deps.storage.clone();
-    let cfg = CONFIG.load(storage)?;
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<StakersResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let total_stake = TOTAL_STAKED.load(deps.storage)?;
+    let undistributed = if total_stake.bonded.is_zero() {
+        Uint128::zero()
+    } else {
+        staker_pending_reward(deps)?.rewards
+    };
 
-    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking.clone(), cfg.bloop_converter_and_staker.clone())?;
-    let withdrawable_amount = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let addr = maybe_addr(deps.api, start_after)?;
+    let start = addr.as_ref().map(Bound::exclusive);
+
+    // a full exit leaves an empty entry behind
+    let stakers = STAKE
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, stake)) if stake.stake.is_zero()))
+        .take(limit)
+        .map(|item| {
+            let (address, stake) = item?;
+            Ok(StakerResponse {
+                address,
+                stake: stake.stake,
+                pending_reward: pending_reward(&cfg, &total_stake, &stake, undistributed)?,
+                power_diff: stake.power_diff,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(StakersResponse { stakers })
+}
+
+pub fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(StatsResponse {
+        stakers: stats.stakers,
+        total_distributed: stats.distributed,
+        total_fees: stats.fees,
+    })
+}
+
+pub fn query_reward_rate(deps: Deps, env: Env, window: RewardWindow) -> StdResult<RewardRateResponse> {
+    let now = env.block.time.seconds();
+    let start = now.saturating_sub(window.seconds());
+
+    // the longer buffers still hold the points the shorter ones dropped
+    let mut points = DAY_REWARDS.all(deps.storage, &env)?.collect::<StdResult<Vec<_>>>()?;
+    points.extend(WEEK_REWARDS.all(deps.storage, &env)?.collect::<StdResult<Vec<_>>>()?);
+    points.extend(MONTH_REWARDS.all(deps.storage, &env)?.collect::<StdResult<Vec<_>>>()?);
+    // measured from the last point at or before the start, else from the oldest one
+    let baseline = points
+        .iter()
+        .filter(|(time, _)| *time <= start)
+        .max_by_key(|(time, _)| *time)
+        .or_else(|| points.iter().min_by_key(|(time, _)| *time));
+    let (since, baseline) = match baseline {
+        Some(baseline) => baseline,
+        None => return Ok(RewardRateResponse { rate: Decimal256::zero(), distributed: Uint128::zero(), since: now }),
+    };
+
+    let power = TOTAL_STAKED.load(deps.storage)?.power;
+    let distributed = STATS.may_load(deps.storage)?.unwrap_or_default().distributed;
+    let elapsed = now - since;
+    let rate = if elapsed == 0 {
+        Decimal256::zero()
+    } else {
+        Decimal256::new(power.checked_sub(baseline.power)?.atomics() / Uint256::from(elapsed))
+    };
+    Ok(RewardRateResponse {
+        rate,
+        distributed: distributed.checked_sub(baseline.distributed)?,
+        since: *since,
+    })
+}
 
-    let total_staked = TOTAL_STAKED.load(storage)?;
-    let pending_treasury_rewards = total_staked.pending_treasury_rewards.checked_add(Decimal::from_atomics(withdrawable_amount, 0).unwrap().checked_mul(cfg.treasury_fee).unwrap().to_uint_floor()).unwrap();
+pub fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let stake = STAKE.may_load(deps.storage, &address)?.unwrap_or_default();
+    Ok(BalanceResponse { balance: stake.stake })
+}
 
-    Ok(RewardResponse { rewards: pending_treasury_rewards })
+pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
+    let total_staked = TOTAL_STAKED.load(deps.storage)?;
+    Ok(TokenInfoResponse {
+        name: SHARE_NAME.to_string(),
+        symbol: SHARE_SYMBOL.to_string(),
+        decimals: SHARE_DECIMALS,
+        total_supply: total_staked.staked,
+    })
 }
 
-pub fn query_syne_staking_reward(
+pub fn query_pending_fees(
     deps: Deps,
+) -> StdResult<PendingFeesResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let mut fees = PENDING_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(address, amount)| PendingFee { address, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // the rewards waiting in the converter are split at the current shares
+    let undistributed = staker_pending_reward(deps)?.rewards;
+    for recipient in cfg.fee_recipients {
+        let fee = fee_of(undistributed, recipient.share).map_err(|err| StdError::generic_err(err.to_string()))?;
+        match fees.iter_mut().find(|pending| pending.address == recipient.address) {
+            Some(pending) => pending.amount = pending.amount.checked_add(fee)?,
+            None if !fee.is_zero() => fees.push(PendingFee { address: recipient.address, amount: fee }),
+            None => {},
+        }
+    }
+    Ok(PendingFeesResponse { fees })
+}
+
+/// SYNE distribution as it would be after an update at the current block
+fn accrued_syne_distribution(
+    deps: Deps,
+    env: &Env,
+) -> StdResult<Option<(SyneDistributionConfig, CurrentSyneDistribution)>> {
+    let Some(syne_config) = SYNE_DISTRIBUTION_CONFIG.may_load(deps.storage)? else {
+        return Ok(None);
+    };
+    let staked = TOTAL_STAKED.load(deps.storage)?.bonded;
+    let current = accrue_syne(&syne_config, CURRENT_SYNE_DISTRIBUTION.load(deps.storage)?, staked, env.block.time.seconds())
+        .map_err(reward_error)?;
+    Ok(Some((syne_config, current)))
+}
+
+pub fn query_syne_reward(
+    deps: Deps,
+    env: Env,
+    address: String,
 ) -> StdResult<RewardResponse> {
-    let storage = deps.storage.clone();
-    let cfg = CONFIG.load(storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let Some((_, current)) = accrued_syne_distribution(deps, &env)? else {
+        return Ok(RewardResponse { rewards: Uint128::zero() });
+    };
 
-    let user_reward_response = query_loop_protocol_staking_rewards(deps.querier, cfg.loop_protocol_staking.clone(), cfg.bloop_converter_and_staker.clone())?;
-    let withdrawable_amount = user_reward_response.user_reward.checked_add(user_reward_response.pending_reward).unwrap();
+    let stake = bonded_stake(deps.storage, &address)?;
+    let power_diff = SYNE_POWER_DIFF.may_load(deps.storage, &address)?.unwrap_or_default();
+    Ok(RewardResponse { rewards: earned_rewards(current.power, power_diff, stake).map_err(reward_error)? })
+}
 
-    let total_staked = TOTAL_STAKED.load(storage)?;
-    let pending_syne_staking_rewards = total_staked.pending_syne_staking_rewards.checked_add(Decimal::from_atomics(withdrawable_amount, 0).unwrap().checked_mul(cfg.syne_staking_fee).unwrap().to_uint_floor()).unwrap();
+pub fn query_syne_distribution(
+    deps: Deps,
+    env: Env,
+) -> StdResult<SyneDistributionResponse> {
+    let (syne_config, current) = accrued_syne_distribution(deps, &env)?
+        .ok_or_else(|| StdError::not_found("syne distribution"))?;
+    Ok(SyneDistributionResponse {
+        syne_addr: syne_config.syne_addr,
+        distribution_per_day: syne_config.distribution_per_day,
+        total_distributed: current.total_distributed,
+        pending: current.pending,
+        power: current.power,
+    })
+}
+
+/// Reward streams as they would be after an update at the current block
+fn accrued_reward_streams(
+    deps: Deps,
+    env: &Env,
+) -> StdResult<Vec<(AssetInfoValidated, RewardStream)>> {
+    let staked = TOTAL_STAKED.load(deps.storage)?.bonded;
+    REWARD_STREAMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (asset, stream) = item?;
+            Ok((asset, accrue_stream(stream, staked, env.block.time.seconds()).map_err(reward_error)?))
+        })
+        .collect()
+}
 
-    Ok(RewardResponse { rewards: pending_syne_staking_rewards })
+pub fn query_reward_streams(
+    deps: Deps,
+    env: Env,
+) -> StdResult<RewardStreamsResponse> {
+    let streams = accrued_reward_streams(deps, &env)?
+        .into_iter()
+        .map(|(asset, stream)| RewardStreamResponse {
+            asset,
+            power: stream.power,
+            remaining: stream.remaining,
+            total_distributed: stream.total_distributed,
+            end: stream.end,
+        })
+        .collect();
+    Ok(RewardStreamsResponse { streams })
+}
+
+pub fn query_stream_rewards(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<StreamRewardsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let stake = bonded_stake(deps.storage, &address)?;
+
+    let rewards = accrued_reward_streams(deps, &env)?
+        .into_iter()
+        .map(|(asset, stream)| {
+            let power_diff = STREAM_POWER_DIFF.may_load(deps.storage, (&address, &asset))?.unwrap_or_default();
+            Ok(asset.with_balance(earned_rewards(stream.power, power_diff, stake).map_err(reward_error)?))
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(StreamRewardsResponse { rewards })
+}
+
+/// Stake of `address` at the start of block `height`, the current block by default.
+/// Measured against the current minimum bond.
+pub fn query_voting_power_at_height(
+    deps: Deps,
+    env: Env,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<VotingPowerAtHeightResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let height = height.unwrap_or(env.block.height);
+    let stake = STAKE.may_load_at_height(deps.storage, &address, height)?.unwrap_or_default().stake;
+    let power = bonded(stake, CONFIG.load(deps.storage)?.min_bond);
+    Ok(VotingPowerAtHeightResponse { power, height })
+}
+
+/// Bonded stake at the start of block `height`, the current block by default
+pub fn query_total_power_at_height(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> StdResult<TotalPowerAtHeightResponse> {
+    let height = height.unwrap_or(env.block.height);
+    let power = TOTAL_STAKED.may_load_at_height(deps.storage, height)?.unwrap_or_default().bonded;
+    Ok(TotalPowerAtHeightResponse { power, height })
+}
+
+pub fn query_info(deps: Deps) -> StdResult<InfoResponse> {
+    Ok(InfoResponse { info: get_contract_version(deps.storage)? })
+}
+
+/// First version without the admin in the config
+fn v1_1_version() -> Version {
+    "1.1.0".parse().unwrap()
+}
+
+/// Turns the fixed treasury and SYNE staking fees of a v1.0 vault into fee recipients,
+/// who keep the fees taken so far as their pending balances
+fn migrate_fixed_fees(storage: &mut dyn Storage, old_cfg: v1::Config, admin: &Addr) -> Result<(), ContractError> {
+    let fees = v1::TOTAL_STAKED.may_load(storage)?.unwrap_or_default();
+    let (treasury, syne_staking) = old_cfg.fee_addresses(admin);
+    for (address, amount) in [(treasury, fees.pending_treasury_rewards), (syne_staking, fees.pending_syne_staking_rewards)] {
+        if !amount.is_zero() {
+            PENDING_FEES.update(storage, &address, |pending| -> StdResult<_> {
+                Ok(pending.unwrap_or_default().checked_add(amount)?)
+            })?;
+        }
+    }
+    CONFIG.save(storage, &old_cfg.into_config(admin))?;
+    Ok(())
 }
 
 /// Manages the contract migration.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let original_version = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let unsupported = || ContractError::UnsupportedMigration {
+        contract: CONTRACT_NAME.to_string(),
+        version: original_version.to_string(),
+    };
+
+    let mut res = match msg {
+        MigrateMsg::FromV1 {} => {
+            if original_version >= v1_1_version() {
+                return Err(unsupported());
+            }
 
-    Ok(Response::new())
+            // `UpdateAdmin` only changed the admin item, so it wins over the config
+            let old_cfg = v1::CONFIG.load(deps.storage)?;
+            let admin = ADMIN.get(deps.as_ref())?.unwrap_or_else(|| old_cfg.admin.clone());
+            ADMIN.set(deps.branch(), Some(admin.clone()))?;
+            let min_bond = old_cfg.min_bond;
+            migrate_fixed_fees(deps.storage, old_cfg, &admin)?;
+
+            let stakers = STAKE
+                .range(deps.storage, None, None, Order::Ascending)
+                .filter(|item| !matches!(item, Ok((_, stake)) if stake.stake.is_zero()))
+                .count();
+            STATS.save(deps.storage, &VaultStats { stakers: stakers as u64, ..VaultStats::default() })?;
+
+            let mut total_stake = TOTAL_STAKED.load(deps.storage)?;
+            total_stake.bonded = bonded_total(deps.storage, min_bond)?;
+            TOTAL_STAKED.save(deps.storage, &total_stake, env.block.height)?;
+
+            Response::new()
+                .add_attribute("action", "migrate")
+                .add_attribute("from_version", original_version.to_string())
+                .add_attribute("admin", admin)
+        }
+    };
+
+    // the tokens of a stake still waiting for its harvest are already in the vault, so it is
+    // settled here. Any other waiting action moved no funds and can simply be sent again.
+    if let Some(v1::RewardAction::Stake { address, amount }) = v1::REWARD_ACTION.may_load(deps.storage)? {
+        let cfg = CONFIG.load(deps.storage)?;
+        let settled = apply_reward_action(deps.storage, &env, &cfg, RewardAction::Stake { address, amount })?;
+        res = res.add_attributes(settled.attributes).add_submessages(settled.messages);
+    }
+    v1::REWARD_ACTION.remove(deps.storage);
+
+    Ok(res)
 }
@@ -1,45 +1,119 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cw20::Cw20ReceiveMsg;
+use cw_controllers::{IsPausedResponse, OwnershipProposalResponse, PausableOperation};
+use cw_core_macros::voting_query;
+use cw_utils::Expiration;
 
-use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Decimal256, Uint128};
+use synedex::asset::{AssetInfo, AssetInfoValidated, AssetValidated};
+use synedex::multi_hop::SwapOperation;
+
+use crate::state::{FeeRecipient, DAY};
 
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Change the admin
-    UpdateAdmin { admin: Option<String> },
+    /// Proposes a new admin, who has `expires_in` seconds to claim the ownership
+    ProposeNewOwner { owner: String, expires_in: u64 },
+    DropOwnershipProposal {},
+    /// Makes the proposed admin the new admin
+    ClaimOwnership {},
     
     UpdateConfig {
         min_bond: Option<Uint128>,
-        treasury_wallet: Option<String>,
-        treasury_withdrawer: Option<String>,
-        syne_staking_reward_distributor: Option<String>,
-        treasury_fee: Option<Decimal>,
-        syne_staking_fee: Option<Decimal>,
+        /// Replaces the fee recipients. Fees already taken stay with the former ones.
+        fee_recipients: Option<Vec<FeeRecipientMsg>>,
+        unbonding_period: Option<u64>,
+        /// Multi-hop router used by `WithdrawRewardsAs`
+        swap_router: Option<String>,
     },
 
     /// This accepts a properly-encoded ReceiveMsg from a cw20 contract
     Receive(Cw20ReceiveMsg),
 
-    /// Withdraws rewards which were previously distributed and assigned to sender.
+    /// Withdraws the rewards of `owner`, the sender by default, and sends them to `receiver`, the
+    /// sender by default. The sender has to be `owner` or the address `owner` delegated its
+    /// withdrawals to with `DelegateWithdrawal`.
     WithdrawRewards {
-        address: String,
+        owner: Option<String>,
+        receiver: Option<String>,
     },
 
-    WithdrawTreasuryRewards {
-        amount: Option<Uint128>,
+    /// Allows `delegated` to withdraw the rewards of the sender, besides the sender itself.
+    /// Replaces the previous delegate, delegating to the sender removes it.
+    DelegateWithdrawal {
+        delegated: String,
     },
 
-    WithdrawSyneStakingRewards {
-        amount: Option<Uint128>,
+    /// Withdraws the rewards of the sender like `WithdrawRewards`, swapping the ones in the staked
+    /// token into `ask_asset` through the swap router. `operations` default to the pair of the
+    /// staked token and `ask_asset`, and the swap fails if it pays less than `minimum_receive`.
+    /// The rewards of an auto-compounding sender are swapped as well, this time.
+    WithdrawRewardsAs {
+        ask_asset: AssetInfo,
+        max_spread: Option<Decimal>,
+        operations: Option<Vec<SwapOperation>>,
+        minimum_receive: Uint128,
     },
 
+    /// Harvests and sends every fee recipient its pending fees, callable by anyone
+    FlushFees {},
+
+    /// Unstakes `amount`. With an unbonding period the tokens stop earning rewards right away
+    /// and can be claimed once it passed.
     Unstake {
         amount: Uint128,
     },
 
+    /// Sends the unstaked tokens whose unbonding period has passed
+    Claim {},
+
+    /// Opts the sender in or out of adding their rewards to their stake instead of receiving them
+    SetAutoCompound { enabled: bool },
+
+    /// Harvests and compounds the rewards of the next batch of opted-in stakers, callable by
+    /// anyone. Each call continues where the previous one stopped.
+    Compound {},
+
     DistributeRewards {
         address: Addr,
-    }
+    },
+
+    /// Sets the SYNE token and how much of it is emitted to the stakers per day
+    UpdateSyneDistribution {
+        syne_addr: Option<String>,
+        distribution_per_day: Option<u64>,
+    },
+
+    /// Opens a reward stream in `asset` next to the bToken rewards, admin only
+    AddRewardStream { asset: AssetInfo },
+
+    /// Funds the reward stream of the sent native token. The rewards are spread until `duration`
+    /// seconds from now, or the end of the stream if later, and distributed at once without one.
+    FundRewards { duration: Option<u64> },
+
+    /// Halts operations, callable by the admin or the guardian
+    Pause { operations: Vec<PausableOperation> },
+
+    /// Resumes operations, callable by the admin
+    Unpause { operations: Vec<PausableOperation> },
+
+    UpdateGuardian { guardian: Option<String> },
+
+    /// Registers a contract to be notified of every stake change, admin only
+    AddHook { addr: String },
+
+    /// Stops notifying a contract, admin only
+    RemoveHook { addr: String },
+
+    // The vault is the cw20 share token (sbLOOP) of its stakes. Transfers move the stake with
+    // its voting power, both sides are paid the rewards they earned so far. Halted along with
+    // withdrawals, as the stake leaves its owner.
+    Transfer { recipient: String, amount: Uint128 },
+    Send { contract: String, amount: Uint128, msg: Binary },
+    TransferFrom { owner: String, recipient: String, amount: Uint128 },
+    SendFrom { owner: String, contract: String, amount: Uint128, msg: Binary },
+    IncreaseAllowance { spender: String, amount: Uint128, expires: Option<Expiration> },
+    DecreaseAllowance { spender: String, amount: Uint128, expires: Option<Expiration> },
 }
 
 #[cw_serde]
@@ -47,14 +121,24 @@ pub enum Cw20HookMsg {
     Stake {},
     DistributeRewards {
         address: Option<Addr>
-    }
+    },
+    /// Tops up the SYNE emitted to the stakers, sent by the admin
+    FundSyne {},
+    /// Funds the reward stream of the sent token, like `ExecuteMsg::FundRewards`
+    FundRewards { duration: Option<u64> },
 }
 
+/// Also the DAO voting module interface, the voting power being the stake
+#[voting_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(AdminResponse)]
     Admin {},
+    #[returns(IsPausedResponse)]
+    IsPaused {},
+    #[returns(OwnershipProposalResponse)]
+    OwnershipProposal {},
     #[returns(ConfigResponse)]
     Config {},
     /// Show the number of tokens currently staked.
@@ -65,15 +149,50 @@ pub enum QueryMsg {
     Stake { address: String },
     #[returns(RewardResponse)]
     Reward { address: String },
+    /// Address allowed to withdraw the rewards of `owner` besides itself
+    #[returns(DelegatedWithdrawalResponse)]
+    DelegatedWithdrawal { owner: String },
     #[returns(RewardResponse)]
     TotalPendingReward {},
+    /// Fees taken and not sent yet, including those of the rewards waiting in the converter
+    #[returns(PendingFeesResponse)]
+    PendingFees {},
+    /// SYNE earned by this address and not paid out yet
     #[returns(RewardResponse)]
-    TreasuryReward {},
-    #[returns(RewardResponse)]
-    SyneStakingReward {},
-    // /// Claims shows the tokens in process of unbonding for this address
-    // #[returns(cw_controllers::ClaimsResponse)]
-    // Claims { address: String },
+    SyneReward { address: String },
+    #[returns(SyneDistributionResponse)]
+    SyneDistribution {},
+    #[returns(RewardStreamsResponse)]
+    RewardStreams {},
+    /// Rewards earned by this address in every reward stream and not paid out yet
+    #[returns(StreamRewardsResponse)]
+    StreamRewards { address: String },
+    /// Shows the unstaked tokens waiting for the unbonding period of this address
+    #[returns(cw_controllers::ClaimsResponse)]
+    Claims { address: String },
+    /// Contracts notified of every stake change
+    #[returns(cw_controllers::HooksResponse)]
+    Hooks {},
+    /// Lists the addresses with a stake, ordered by address
+    #[returns(StakersResponse)]
+    ListStakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Number of stakers and the rewards and fees distributed so far
+    #[returns(StatsResponse)]
+    Stats {},
+    /// Rewards distributed per staked token per second over the trailing `window`
+    #[returns(RewardRateResponse)]
+    RewardRate { window: RewardWindow },
+    /// Shares of this address, the same as its stake
+    #[returns(cw20::BalanceResponse)]
+    Balance { address: String },
+    /// The share token, whose supply is the total stake
+    #[returns(cw20::TokenInfoResponse)]
+    TokenInfo {},
+    #[returns(cw20::AllowanceResponse)]
+    Allowance { owner: String, spender: String },
     // /// Show the number of tokens currently staked by this address.
     // #[returns(StakedResponse)]
     // Staked {
@@ -129,9 +248,9 @@ pub enum QueryMsg {
 }
 
 #[cw_serde]
-pub struct MigrateMsg {
-    // /// Address of the account that can call [`ExecuteMsg::QuickUnbond`]
-    // pub unbonder: Option<String>,
+pub enum MigrateMsg {
+    /// Migrates a v1.0 vault, which kept the admin in its config besides the admin item
+    FromV1 {},
 }
 
 #[cw_serde]
@@ -150,28 +269,129 @@ pub struct ConfigResponse {
     pub bloop_converter_and_staker: Addr,
     pub loop_protocol_staking: Addr,
     pub min_bond: Uint128,
-    pub treasury_wallet: Option<Addr>,
-    pub treasury_withdrawer: Option<Addr>,
-    pub treasury_fee: Decimal,
-    pub syne_staking_reward_distributor: Option<Addr>,
-    pub syne_staking_fee: Decimal,
+    pub fee_recipients: Vec<FeeRecipient>,
+    pub total_fee_cap: Decimal,
     pub duration: u64,
+    pub unbonding_period: u64,
+    pub swap_router: Option<Addr>,
 }
 
 #[cw_serde]
 pub struct StakedResponse {
     pub stake: Uint128,
-    pub power_diff: Decimal,
+    pub power_diff: Decimal256,
+    pub auto_compound: bool,
+}
+
+#[cw_serde]
+pub struct DelegatedWithdrawalResponse {
+    pub delegated: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct StakerResponse {
+    pub address: Addr,
+    pub stake: Uint128,
+    /// Rewards earned and not withdrawn yet, like `QueryMsg::Reward`
+    pub pending_reward: Uint128,
+    pub power_diff: Decimal256,
+}
+
+#[cw_serde]
+pub struct StakersResponse {
+    pub stakers: Vec<StakerResponse>,
+}
+
+#[cw_serde]
+pub struct StatsResponse {
+    pub stakers: u64,
+    pub total_distributed: Uint128,
+    pub total_fees: Uint128,
+}
+
+#[cw_serde]
+pub enum RewardWindow {
+    Day,
+    Week,
+    Month,
+}
+
+impl RewardWindow {
+    pub fn seconds(&self) -> u64 {
+        match self {
+            RewardWindow::Day => DAY,
+            RewardWindow::Week => 7 * DAY,
+            RewardWindow::Month => 30 * DAY,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RewardRateResponse {
+    /// Rewards per staked token per second, times the seconds of a year for the APR
+    pub rate: Decimal256,
+    /// Rewards distributed to the stakers since `since`
+    pub distributed: Uint128,
+    /// Time of the recorded distribution the rate is measured from, at or shortly before the
+    /// start of the window once there is enough history
+    pub since: u64,
 }
 
 #[cw_serde]
 pub struct TotalStakedResponse {
     pub total_staked: Uint128,
-    pub power: Decimal,
-    pub pending_treasury_rewards: Uint128
+    pub power: Decimal256,
+}
+
+#[cw_serde]
+pub struct FeeRecipientMsg {
+    pub address: String,
+    pub share: Decimal,
+    pub label: String,
+}
+
+#[cw_serde]
+pub struct PendingFee {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingFeesResponse {
+    pub fees: Vec<PendingFee>,
 }
 
 #[cw_serde]
 pub struct RewardResponse {
     pub rewards: Uint128,
 }
+
+#[cw_serde]
+pub struct SyneDistributionResponse {
+    pub syne_addr: String,
+    pub distribution_per_day: u64,
+    pub total_distributed: Uint128,
+    /// Funded but not emitted yet
+    pub pending: Uint128,
+    pub power: Decimal256,
+}
+
+#[cw_serde]
+pub struct RewardStreamResponse {
+    pub asset: AssetInfoValidated,
+    pub power: Decimal256,
+    /// Funded but not emitted yet
+    pub remaining: Uint128,
+    pub total_distributed: Uint128,
+    pub end: u64,
+}
+
+#[cw_serde]
+pub struct RewardStreamsResponse {
+    pub streams: Vec<RewardStreamResponse>,
+}
+
+#[cw_serde]
+pub struct StreamRewardsResponse {
+    pub rewards: Vec<AssetValidated>,
+}
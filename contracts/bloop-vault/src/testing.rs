@@ -0,0 +1,1558 @@
+use std::str::FromStr;
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, ContractResult, CosmosMsg, Decimal, Decimal256, Env, Order,
+    OwnedDeps, Reply, Response, SubMsg,
+    SubMsgResponse, SubMsgResult, SystemResult, Uint128, Uint256, WasmMsg,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{AllowanceResponse, BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg, TokenInfoResponse};
+use cw_controllers::{ClaimsResponse, HooksResponse, PausableError, PausableOperation};
+use cw_core_interface::voting::{InfoResponse, TotalPowerAtHeightResponse, VotingPowerAtHeightResponse};
+use cw_utils::Expiration;
+
+use synedao::bloop_vault::InstantiateMsg;
+use synedex::asset::{native_asset, native_asset_info, token_asset, token_asset_info, AssetInfo, AssetInfoValidated};
+use synedex::multi_hop::{Cw20HookMsg as MultiHopCw20HookMsg, SwapOperation};
+
+use crate::contract::{execute, instantiate, migrate, query, query_admin, query_staked, remove_stake, reply, update_total_rewards};
+use crate::hook::{MemberChangedHookMsg, MemberDiff};
+use crate::msg::{
+    Cw20HookMsg, DelegatedWithdrawalResponse, ExecuteMsg, FeeRecipientMsg, MigrateMsg, PendingFeesResponse, QueryMsg, RewardRateResponse, RewardResponse,
+    RewardWindow,
+    StakersResponse, StatsResponse,
+    StreamRewardsResponse, SyneDistributionResponse, TotalStakedResponse, WithdrawMsg,
+};
+use crate::queriers::UserRewardResponse;
+use crate::state::{Config, FeeRecipient, ADMIN, CONFIG, PENDING_ACTIONS, PENDING_FEES, REWARD_STREAMS, STAKE, TOTAL_STAKED};
+use crate::v1;
+use crate::ContractError;
+
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_CRATE_NAME"));
+
+fn seed_v1(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, version: &str) {
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, version).unwrap();
+    v1::CONFIG
+        .save(
+            deps.as_mut().storage,
+            &v1::Config {
+                admin: Addr::unchecked("admin"),
+                token: Addr::unchecked("bloop"),
+                bloop_converter_and_staker: Addr::unchecked("converter"),
+                min_bond: Uint128::new(1),
+                loop_protocol_staking: Addr::unchecked("staking"),
+                treasury_wallet: Some(Addr::unchecked("treasury")),
+                treasury_withdrawer: None,
+                syne_staking_reward_distributor: None,
+                treasury_fee: Decimal::from_str("0.2").unwrap(),
+                syne_staking_fee: Decimal::zero(),
+                total_fee_cap: Decimal::from_str("0.3").unwrap(),
+                treasury_fee_limit: Decimal::from_str("0.05").unwrap(),
+                duration: 12,
+            },
+        )
+        .unwrap();
+    v1::TOTAL_STAKED
+        .save(
+            deps.as_mut().storage,
+            &v1::TokenInfo {
+                staked: Uint128::new(5000),
+                pending_treasury_rewards: Uint128::new(20),
+                pending_syne_staking_rewards: Uint128::zero(),
+                power: Decimal::percent(3),
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn migrate_from_v1() {
+    let mut deps = mock_dependencies();
+    seed_v1(&mut deps, "1.0.1");
+    ADMIN.set(deps.as_mut(), Some(Addr::unchecked("admin"))).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap();
+
+    assert_eq!(
+        CONFIG.load(deps.as_ref().storage).unwrap(),
+        Config {
+            token: Addr::unchecked("bloop"),
+            bloop_converter_and_staker: Addr::unchecked("converter"),
+            min_bond: Uint128::new(1),
+            loop_protocol_staking: Addr::unchecked("staking"),
+            fee_recipients: vec![FeeRecipient {
+                address: Addr::unchecked("treasury"),
+                share: Decimal::from_str("0.2").unwrap(),
+                label: "treasury".to_string(),
+            }],
+            total_fee_cap: Decimal::from_str("0.3").unwrap(),
+            duration: 12,
+            unbonding_period: 0,
+            swap_router: None,
+        }
+    );
+    assert_eq!(
+        query_admin(deps.as_ref()).unwrap().admin,
+        Addr::unchecked("admin")
+    );
+
+    // stake accounting is untouched, the treasury keeps its fees
+    let total = TOTAL_STAKED.load(deps.as_ref().storage).unwrap();
+    assert_eq!(total.staked, Uint128::new(5000));
+    assert_eq!(total.power, Decimal256::percent(3));
+    assert_eq!(
+        PENDING_FEES.load(deps.as_ref().storage, &Addr::unchecked("treasury")).unwrap(),
+        Uint128::new(20)
+    );
+
+    let version = get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn migrate_from_v1_keeps_updated_admin() {
+    // `UpdateAdmin` moved the admin item away from the config
+    let mut deps = mock_dependencies();
+    seed_v1(&mut deps, "1.0.0");
+    ADMIN.set(deps.as_mut(), Some(Addr::unchecked("new_admin"))).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap();
+    assert_eq!(
+        query_admin(deps.as_ref()).unwrap().admin,
+        Addr::unchecked("new_admin")
+    );
+
+    // a cleared admin item falls back to the config
+    let mut deps = mock_dependencies();
+    seed_v1(&mut deps, "1.0.1");
+    ADMIN.set(deps.as_mut(), None).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap();
+    assert_eq!(
+        query_admin(deps.as_ref()).unwrap().admin,
+        Addr::unchecked("admin")
+    );
+}
+
+#[test]
+fn migrate_refuses_wrong_layout_and_downgrade() {
+    // a vault already on this version has no v1.0 layout left
+    let mut deps = mock_dependencies();
+    seed_v1(&mut deps, "1.1.0");
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedMigration {
+            contract: CONTRACT_NAME.to_string(),
+            version: "1.1.0".to_string(),
+        }
+    );
+
+    let mut deps = mock_dependencies();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap_err();
+    let version = get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.version, "99.0.0");
+
+    let mut deps = mock_dependencies();
+    set_contract_version(deps.as_mut().storage, "crates.io:bwynd_vault", "1.0.1").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap_err();
+}
+
+#[test]
+fn migrate_settles_waiting_stake() {
+    let mut deps = mock_dependencies();
+    seed_v1(&mut deps, "1.0.1");
+    ADMIN.set(deps.as_mut(), Some(Addr::unchecked("admin"))).unwrap();
+    v1::REWARD_ACTION
+        .save(
+            deps.as_mut().storage,
+            &v1::RewardAction::Stake {
+                address: Addr::unchecked("alice"),
+                amount: Uint128::new(300),
+            },
+        )
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap();
+
+    let stake = STAKE.load(deps.as_ref().storage, &Addr::unchecked("alice")).unwrap();
+    assert_eq!(stake.stake, Uint128::new(300));
+    assert_eq!(stake.power_diff, Decimal256::percent(3));
+    let total = TOTAL_STAKED.load(deps.as_ref().storage).unwrap();
+    assert_eq!(total.staked, Uint128::new(5300));
+    // counted from the stakes themselves, alice's is the only one
+    assert_eq!(total.bonded, Uint128::new(300));
+    assert!(v1::REWARD_ACTION.may_load(deps.as_ref().storage).unwrap().is_none());
+}
+
+#[test]
+fn migrate_from_v1_keeps_pending_fees() {
+    let mut deps = mock_dependencies();
+    seed_v1(&mut deps, "1.0.1");
+    ADMIN.set(deps.as_mut(), Some(Addr::unchecked("admin"))).unwrap();
+    // the SYNE staking fee was lowered to 0 before a distributor was ever set
+    let mut total = v1::TOTAL_STAKED.load(deps.as_ref().storage).unwrap();
+    total.pending_syne_staking_rewards = Uint128::new(5);
+    v1::TOTAL_STAKED.save(deps.as_mut().storage, &total).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromV1 {}).unwrap();
+
+    let cfg = CONFIG.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        cfg.fee_recipients,
+        vec![FeeRecipient {
+            address: Addr::unchecked("treasury"),
+            share: Decimal::from_str("0.2").unwrap(),
+            label: "treasury".to_string(),
+        }]
+    );
+    let fees: Vec<_> = PENDING_FEES
+        .range(deps.as_ref().storage, None, None, Order::Ascending)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        fees,
+        vec![(Addr::unchecked("admin"), Uint128::new(5)), (Addr::unchecked("treasury"), Uint128::new(20))]
+    );
+    assert_eq!(TOTAL_STAKED.load(deps.as_ref().storage).unwrap().staked, Uint128::new(5000));
+}
+
+fn set_pending_rewards(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, rewards: u128) {
+    deps.querier.update_wasm(move |_| {
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&UserRewardResponse {
+                user_reward: Uint128::new(rewards),
+                calculated_days_of_reward: 0,
+                pending_reward: Uint128::zero(),
+            })
+            .unwrap(),
+        ))
+    });
+}
+
+fn receive(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    sender: &str,
+    amount: u128,
+    msg: Cw20HookMsg,
+) -> Response {
+    receive_at(deps, mock_env(), "bloop", sender, amount, msg).unwrap()
+}
+
+fn receive_at(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    env: Env,
+    token: &str,
+    sender: &str,
+    amount: u128,
+    msg: Cw20HookMsg,
+) -> Result<Response, ContractError> {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(token, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&msg).unwrap(),
+        }),
+    )
+}
+
+fn harvest_reply(id: u64, result: SubMsgResult) -> Reply {
+    Reply { id, result }
+}
+
+fn instantiate_vault(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("converter", &[]),
+        InstantiateMsg {
+            admin: "admin".to_string(),
+            token: "bloop".to_string(),
+            loop_protocol_staking: "staking".to_string(),
+            min_bond: Uint128::new(1),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn actions_wait_for_their_harvest() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+
+    // nothing to harvest, the stake is booked right away
+    set_pending_rewards(&mut deps, 0);
+    let res = receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    assert!(res.messages.is_empty());
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::new(1000));
+
+    // with rewards waiting, any number of users act in the same block
+    set_pending_rewards(&mut deps, 100);
+    let harvest = |id| {
+        SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: "converter".to_string(),
+                msg: to_binary(&WithdrawMsg::WithdrawRewards {}).unwrap(),
+                funds: vec![],
+            },
+            id,
+        )
+    };
+    let res = receive(&mut deps, "bob", 500, Cw20HookMsg::Stake {});
+    assert_eq!(res.messages, vec![harvest(0)]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawRewards { owner: None, receiver: None },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![harvest(1)]);
+    assert_eq!(query_staked(deps.as_ref(), "bob".to_string()).unwrap().stake, Uint128::zero());
+
+    // the first harvest distributes the rewards, 20% go to the treasury
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        harvest_reply(0, SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None })),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(query_staked(deps.as_ref(), "bob".to_string()).unwrap().stake, Uint128::new(500));
+
+    // the second one finds nothing left, alice is paid all the same
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        harvest_reply(1, SubMsgResult::Err("nothing to harvest".to_string())),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "alice".to_string(),
+                amount: Uint128::new(80),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert!(res.attributes.iter().any(|attr| attr.key == "harvest_error"));
+
+    // nothing is left waiting
+    assert!(PENDING_ACTIONS
+        .range(deps.as_ref().storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        harvest_reply(1, SubMsgResult::Err("nothing to harvest".to_string())),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::UnknownReply(1));
+}
+
+fn syne_reward(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env, address: &str) -> u128 {
+    let res: RewardResponse = from_binary(
+        &query(deps.as_ref(), env.clone(), QueryMsg::SyneReward { address: address.to_string() }).unwrap(),
+    )
+    .unwrap();
+    res.rewards.u128()
+}
+
+fn syne_transfers(res: &Response) -> Vec<Cw20ExecuteMsg> {
+    res.messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) if contract_addr == "syne" => {
+                Some(from_binary(msg).unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn syne_emissions() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let mut env = mock_env();
+
+    // only the admin sets up the distribution, and it cannot be funded before
+    let err = receive_at(&mut deps, env.clone(), "syne", "admin", 100, Cw20HookMsg::FundSyne {}).unwrap_err();
+    assert_eq!(err, ContractError::SyneNotConfigured {});
+    let update = ExecuteMsg::UpdateSyneDistribution {
+        syne_addr: Some("syne".to_string()),
+        distribution_per_day: Some(8640),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), update.clone()).unwrap_err();
+    execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), update).unwrap();
+
+    // funding comes from the admin, in SYNE
+    receive_at(&mut deps, env.clone(), "syne", "alice", 100, Cw20HookMsg::FundSyne {}).unwrap_err();
+    receive_at(&mut deps, env.clone(), "bloop", "admin", 100, Cw20HookMsg::FundSyne {}).unwrap_err();
+    receive_at(&mut deps, env.clone(), "syne", "admin", 20_000, Cw20HookMsg::FundSyne {}).unwrap();
+
+    // nothing is emitted into an empty vault
+    env.block.time = env.block.time.plus_seconds(86400);
+    receive_at(&mut deps, env.clone(), "bloop", "alice", 1000, Cw20HookMsg::Stake {}).unwrap();
+    assert_eq!(syne_reward(&deps, &env, "alice"), 0);
+
+    env.block.time = env.block.time.plus_seconds(86400);
+    assert_eq!(syne_reward(&deps, &env, "alice"), 8640);
+    let res = receive_at(&mut deps, env.clone(), "bloop", "bob", 1000, Cw20HookMsg::Stake {}).unwrap();
+    assert!(syne_transfers(&res).is_empty());
+
+    // the stakers share the emission from then on
+    env.block.time = env.block.time.plus_seconds(86400);
+    assert_eq!(syne_reward(&deps, &env, "alice"), 8640 + 4320);
+    assert_eq!(syne_reward(&deps, &env, "bob"), 4320);
+
+    // paid out on withdraw and on unstake
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawRewards { owner: None, receiver: None },
+    )
+    .unwrap();
+    assert_eq!(
+        syne_transfers(&res),
+        vec![Cw20ExecuteMsg::Transfer { recipient: "alice".to_string(), amount: Uint128::new(12960) }]
+    );
+    assert_eq!(syne_reward(&deps, &env, "alice"), 0);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &[]),
+        ExecuteMsg::Unstake { amount: Uint128::new(1000) },
+    )
+    .unwrap();
+    assert_eq!(
+        syne_transfers(&res),
+        vec![Cw20ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::new(4320) }]
+    );
+
+    // emissions stop when the funds run out
+    env.block.time = env.block.time.plus_seconds(86400 * 5);
+    assert_eq!(syne_reward(&deps, &env, "alice"), 20_000 - 8640 * 2);
+    let res: SyneDistributionResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::SyneDistribution {}).unwrap()).unwrap();
+    assert_eq!(res.total_distributed, Uint128::new(20_000));
+    assert_eq!(res.pending, Uint128::zero());
+
+    // the token is fixed once funded, the rate is not
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateSyneDistribution { syne_addr: Some("other".to_string()), distribution_per_day: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SyneTokenLocked {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateSyneDistribution { syne_addr: None, distribution_per_day: Some(0) },
+    )
+    .unwrap();
+}
+
+fn stream_rewards(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env, address: &str) -> Vec<(String, u128)> {
+    let res: StreamRewardsResponse = from_binary(
+        &query(deps.as_ref(), env.clone(), QueryMsg::StreamRewards { address: address.to_string() }).unwrap(),
+    )
+    .unwrap();
+    res.rewards
+        .into_iter()
+        .map(|reward| (reward.info.to_string(), reward.amount.u128()))
+        .collect()
+}
+
+#[test]
+fn reward_streams() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let mut env = mock_env();
+
+    // the admin opens the streams, not in the staked token and not twice
+    let add_stream = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, sender: &str, asset| {
+        execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), ExecuteMsg::AddRewardStream { asset })
+    };
+    add_stream(&mut deps, "alice", native_asset_info("uusdc")).unwrap_err();
+    let err = add_stream(&mut deps, "admin", token_asset_info("bloop")).unwrap_err();
+    assert_eq!(err, ContractError::InvalidAsset {});
+    add_stream(&mut deps, "admin", native_asset_info("uusdc")).unwrap();
+    add_stream(&mut deps, "admin", token_asset_info("partner")).unwrap();
+    let err = add_stream(&mut deps, "admin", native_asset_info("uusdc")).unwrap_err();
+    assert_eq!(err, ContractError::DistributionAlreadyExists(AssetInfoValidated::Native("uusdc".to_string())));
+
+    // funding needs a stream, and stakers when distributed at once
+    let fund = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env, denom: &str, duration| {
+        execute(deps.as_mut(), env.clone(), mock_info("partner_wallet", &coins(1000, denom)), ExecuteMsg::FundRewards { duration })
+    };
+    let err = fund(&mut deps, &env, "uatom", Some(100)).unwrap_err();
+    assert_eq!(err, ContractError::NoRewardStream(AssetInfoValidated::Native("uatom".to_string())));
+    let err = fund(&mut deps, &env, "uusdc", Some(0)).unwrap_err();
+    assert_eq!(err, ContractError::ZeroRewardDuration {});
+    let err = fund(&mut deps, &env, "uusdc", None).unwrap_err();
+    assert_eq!(err, ContractError::NoMembersToDistributeTo {});
+
+    receive_at(&mut deps, env.clone(), "bloop", "alice", 1000, Cw20HookMsg::Stake {}).unwrap();
+    fund(&mut deps, &env, "uusdc", Some(100)).unwrap();
+    receive_at(&mut deps, env.clone(), "partner", "partner_wallet", 500, Cw20HookMsg::FundRewards { duration: None }).unwrap();
+    assert_eq!(stream_rewards(&deps, &env, "alice"), vec![("uusdc".to_string(), 0), ("partner".to_string(), 500)]);
+
+    // bob shares the second half of the stretched funding
+    env.block.time = env.block.time.plus_seconds(50);
+    let res = receive_at(&mut deps, env.clone(), "bloop", "bob", 1000, Cw20HookMsg::Stake {}).unwrap();
+    assert!(res.messages.is_empty());
+    env.block.time = env.block.time.plus_seconds(100);
+    assert_eq!(stream_rewards(&deps, &env, "alice"), vec![("uusdc".to_string(), 750), ("partner".to_string(), 500)]);
+    assert_eq!(stream_rewards(&deps, &env, "bob"), vec![("uusdc".to_string(), 250), ("partner".to_string(), 0)]);
+
+    // every asset is paid out on unstake
+    let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::Unstake { amount: Uint128::new(1000) }).unwrap();
+    let msgs: Vec<_> = res.messages.into_iter().map(|sub| sub.msg).collect();
+    assert!(msgs.contains(&native_asset("uusdc", 750u128).into_msg("alice").unwrap()));
+    assert!(msgs.contains(&token_asset(Addr::unchecked("partner"), 500u128).into_msg("alice").unwrap()));
+    assert_eq!(stream_rewards(&deps, &env, "alice"), vec![("uusdc".to_string(), 0), ("partner".to_string(), 0)]);
+
+    // the number of streams is capped
+    for i in 2..10 {
+        add_stream(&mut deps, "admin", native_asset_info(&format!("denom{}", i))).unwrap();
+    }
+    let err = add_stream(&mut deps, "admin", native_asset_info("denom10")).unwrap_err();
+    assert_eq!(err, ContractError::TooManyDistributions(10));
+}
+
+fn claims(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str) -> Vec<(u128, Expiration)> {
+    let res: ClaimsResponse = from_binary(
+        &query(deps.as_ref(), mock_env(), QueryMsg::Claims { address: address.to_string() }).unwrap(),
+    )
+    .unwrap();
+    res.claims.into_iter().map(|claim| (claim.amount.u128(), claim.release_at)).collect()
+}
+
+#[test]
+fn unbonding_claims() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let mut env = mock_env();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateConfig {
+            min_bond: None,
+            fee_recipients: None,
+            unbonding_period: Some(100),
+            swap_router: None,
+        },
+    )
+    .unwrap();
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "bob", 1000, Cw20HookMsg::Stake {});
+
+    // the unstaked tokens stay in the vault until the unbonding period passed
+    let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::Unstake { amount: Uint128::new(400) }).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::new(600));
+    assert_eq!(claims(&deps, "alice"), vec![(400, Expiration::AtTime(env.block.time.plus_seconds(100)))]);
+
+    // unbonding tokens earn nothing, 80 is left after the treasury fee
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let reward = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str| {
+        let res: RewardResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: address.to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.rewards.u128()
+    };
+    assert_eq!(reward(&deps, "alice"), 30);
+    assert_eq!(reward(&deps, "bob"), 50);
+
+    let err = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::Claim {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "alice".to_string(),
+                amount: Uint128::new(400),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert!(claims(&deps, "alice").is_empty());
+}
+
+#[test]
+fn stake_changes_notify_hooks() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+
+    // only the admin registers hooks
+    let add_hook = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, sender: &str, addr: &str| {
+        execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), ExecuteMsg::AddHook { addr: addr.to_string() })
+    };
+    add_hook(&mut deps, "alice", "gauge").unwrap_err();
+    add_hook(&mut deps, "admin", "gauge").unwrap();
+    add_hook(&mut deps, "admin", "governance").unwrap();
+    let res: HooksResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Hooks {}).unwrap()).unwrap();
+    assert_eq!(res.hooks, vec!["gauge".to_string(), "governance".to_string()]);
+
+    let hook_msgs = |diff: MemberDiff, hooks: &[&str]| -> Vec<SubMsg> {
+        hooks
+            .iter()
+            .map(|hook| SubMsg::new(MemberChangedHookMsg::one(diff.clone()).into_cosmos_msg(*hook).unwrap()))
+            .collect()
+    };
+    let res = receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    assert_eq!(res.messages, hook_msgs(MemberDiff::new("alice", None, Some(Uint128::new(1000))), &["gauge", "governance"]));
+    let res = receive(&mut deps, "alice", 500, Cw20HookMsg::Stake {});
+    assert_eq!(
+        res.messages,
+        hook_msgs(MemberDiff::new("alice", Some(Uint128::new(1000)), Some(Uint128::new(1500))), &["gauge", "governance"])
+    );
+
+    // a full exit removes the member
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), ExecuteMsg::RemoveHook { addr: "governance".to_string() }).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::Unstake { amount: Uint128::new(1500) }).unwrap();
+    assert_eq!(
+        res.messages[..1],
+        hook_msgs(MemberDiff::new("alice", Some(Uint128::new(1500)), None), &["gauge"])[..]
+    );
+}
+
+#[test]
+fn voting_power_at_height() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let mut env = mock_env();
+    let start = env.block.height;
+
+    let power = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env, address: &str, height| {
+        let res: VotingPowerAtHeightResponse = from_binary(
+            &query(deps.as_ref(), env.clone(), QueryMsg::VotingPowerAtHeight { address: address.to_string(), height }).unwrap(),
+        )
+        .unwrap();
+        (res.power.u128(), res.height)
+    };
+    let total_power = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: &Env, height| {
+        let res: TotalPowerAtHeightResponse =
+            from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::TotalPowerAtHeight { height }).unwrap()).unwrap();
+        (res.power.u128(), res.height)
+    };
+
+    receive_at(&mut deps, env.clone(), "bloop", "alice", 1000, Cw20HookMsg::Stake {}).unwrap();
+    env.block.height += 1;
+    receive_at(&mut deps, env.clone(), "bloop", "bob", 500, Cw20HookMsg::Stake {}).unwrap();
+    env.block.height += 1;
+    execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), ExecuteMsg::Unstake { amount: Uint128::new(400) }).unwrap();
+    env.block.height += 1;
+
+    // a height shows the stake at the start of that block
+    assert_eq!(power(&deps, &env, "alice", Some(start)), (0, start));
+    assert_eq!(power(&deps, &env, "alice", Some(start + 1)), (1000, start + 1));
+    assert_eq!(power(&deps, &env, "alice", Some(start + 2)), (1000, start + 2));
+    assert_eq!(power(&deps, &env, "alice", None), (600, start + 3));
+    assert_eq!(power(&deps, &env, "bob", Some(start + 1)), (0, start + 1));
+    assert_eq!(total_power(&deps, &env, Some(start + 1)), (1000, start + 1));
+    assert_eq!(total_power(&deps, &env, Some(start + 2)), (1500, start + 2));
+    assert_eq!(total_power(&deps, &env, None), (1100, start + 3));
+
+    let res: InfoResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::Info {}).unwrap()).unwrap();
+    assert_eq!(res.info.contract, CONTRACT_NAME);
+}
+
+#[test]
+fn list_stakers_and_stats() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "bob", 500, Cw20HookMsg::Stake {});
+    receive(&mut deps, "carol", 300, Cw20HookMsg::Stake {});
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), ExecuteMsg::Unstake { amount: Uint128::new(500) }).unwrap();
+
+    let stats: StatsResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap()).unwrap();
+    assert_eq!(
+        stats,
+        StatsResponse {
+            stakers: 2,
+            total_distributed: Uint128::new(80),
+            total_fees: Uint128::new(20),
+        }
+    );
+
+    // bob left, so carol follows alice
+    let list = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, start_after: Option<&str>| {
+        let res: StakersResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::ListStakers { start_after: start_after.map(String::from), limit: Some(1) }).unwrap(),
+        )
+        .unwrap();
+        res.stakers
+            .into_iter()
+            .map(|staker| (staker.address.to_string(), staker.stake.u128(), staker.pending_reward.u128()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(list(&deps, None), vec![("alice".to_string(), 1000, 44)]);
+    assert_eq!(list(&deps, Some("alice")), vec![("carol".to_string(), 300, 13)]);
+    assert!(list(&deps, Some("carol")).is_empty());
+}
+
+#[test]
+fn min_bond() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let update_min_bond = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, min_bond: u128| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::UpdateConfig {
+                min_bond: Some(Uint128::new(min_bond)),
+                fee_recipients: None,
+                unbonding_period: None,
+                swap_router: None,
+            },
+        )
+        .unwrap();
+    };
+    update_min_bond(&mut deps, 100);
+
+    let err = receive_at(&mut deps, mock_env(), "bloop", "alice", 50, Cw20HookMsg::Stake {}).unwrap_err();
+    assert_eq!(err, ContractError::BelowMinBond(Uint128::new(100)));
+    receive(&mut deps, "alice", 150, Cw20HookMsg::Stake {});
+
+    // the 50 left over would be dust, so alice leaves entirely
+    let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::Unstake { amount: Uint128::new(100) }).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "alice".to_string(),
+                amount: Uint128::new(150),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::zero());
+
+    let reward = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str| {
+        let res: RewardResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: address.to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.rewards.u128()
+    };
+    // as it stands at the start of the next block
+    let total_power = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        let height = Some(mock_env().block.height + 1);
+        let res: TotalPowerAtHeightResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalPowerAtHeight { height }).unwrap()).unwrap();
+        res.power.u128()
+    };
+    receive(&mut deps, "bob", 200, Cw20HookMsg::Stake {});
+    receive(&mut deps, "carol", 300, Cw20HookMsg::Stake {});
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    assert_eq!(reward(&deps, "bob"), 32);
+    assert_eq!(total_power(&deps), 500);
+
+    // a raise pays out what bob earned before his position fell below the bond
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), ExecuteMsg::AddHook { addr: "gauge".to_string() }).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateConfig { min_bond: Some(Uint128::new(300)), fee_recipients: None, unbonding_period: None, swap_router: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "bloop".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::new(32) }).unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(
+                MemberChangedHookMsg::one(MemberDiff::new("bob", Some(Uint128::new(200)), None))
+                    .into_cosmos_msg("gauge")
+                    .unwrap()
+            ),
+        ]
+    );
+    assert_eq!(reward(&deps, "bob"), 0);
+    assert_eq!(total_power(&deps), 300);
+
+    // the stake below the bond takes no share of later rewards, carol gets all of them
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    assert_eq!(reward(&deps, "bob"), 0);
+    assert_eq!(reward(&deps, "carol"), 48 + 79);
+
+    // once bob counts again he only earns what comes after
+    update_min_bond(&mut deps, 100);
+    assert_eq!(total_power(&deps), 500);
+    assert_eq!(reward(&deps, "bob"), 0);
+}
+
+#[test]
+fn auto_compound() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let reward = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str| {
+        let res: RewardResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: address.to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.rewards
+    };
+    let set_auto_compound = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, enabled: bool| {
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::SetAutoCompound { enabled }).unwrap();
+    };
+
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "bob", 1000, Cw20HookMsg::Stake {});
+    set_auto_compound(&mut deps, true);
+    assert!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().auto_compound);
+
+    // withdrawing adds alice's rewards to her stake instead of sending them
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let first = reward(&deps, "alice");
+    assert!(!first.is_zero());
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawRewards { owner: None, receiver: None },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::new(1000) + first);
+    assert_eq!(reward(&deps, "alice"), Uint128::zero());
+
+    // anyone compounds the opted-in stakers, bob keeps his rewards to withdraw
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let second = reward(&deps, "alice");
+    let bob_reward = reward(&deps, "bob");
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::Compound {}).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake,
+        Uint128::new(1000) + first + second
+    );
+    assert_eq!(query_staked(deps.as_ref(), "bob".to_string()).unwrap().stake, Uint128::new(1000));
+    assert_eq!(reward(&deps, "bob"), bob_reward);
+    let total: TotalStakedResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalStake {}).unwrap()).unwrap();
+    assert_eq!(total.total_staked, Uint128::new(2000) + first + second);
+
+    // with rewards waiting in the converter, compounding waits for the harvest
+    set_pending_rewards(&mut deps, 100);
+    let third = reward(&deps, "alice");
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::Compound {}).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        harvest_reply(0, SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None })),
+    )
+    .unwrap();
+    assert_eq!(
+        query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake,
+        Uint128::new(1000) + first + second + third
+    );
+
+    // opting out sends the rewards again
+    set_pending_rewards(&mut deps, 0);
+    set_auto_compound(&mut deps, false);
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let fourth = reward(&deps, "alice");
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::WithdrawRewards { owner: None, receiver: None },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: "alice".to_string(), amount: fourth }).unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+#[test]
+fn auto_compound_on_unstake() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let reward = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        let res: RewardResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: "alice".to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.rewards
+    };
+    let transfer = |amount: Uint128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: "alice".to_string(), amount }).unwrap(),
+            funds: vec![],
+        }))
+    };
+
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "bob", 1000, Cw20HookMsg::Stake {});
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::SetAutoCompound { enabled: true }).unwrap();
+
+    // a partial unstake compounds the rewards into what is left
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let first = reward(&deps);
+    assert!(!first.is_zero());
+    let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::Unstake { amount: Uint128::new(400) }).unwrap();
+    assert_eq!(res.messages, vec![transfer(Uint128::new(400))]);
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::new(600) + first);
+
+    // a full exit has nothing to compound into and sends them along
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let second = reward(&deps);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unstake { amount: Uint128::new(600) + first },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer(Uint128::new(600) + first), transfer(second)]);
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::zero());
+}
+
+#[test]
+fn fee_recipients() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let update_fee_recipients = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, recipients: Vec<(&str, &str, &str)>| {
+        let fee_recipients = recipients
+            .into_iter()
+            .map(|(address, share, label)| FeeRecipientMsg {
+                address: address.to_string(),
+                share: Decimal::from_str(share).unwrap(),
+                label: label.to_string(),
+            })
+            .collect();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::UpdateConfig { min_bond: None, fee_recipients: Some(fee_recipients), unbonding_period: None, swap_router: None },
+        )
+    };
+    let pending_fees = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        let res: PendingFeesResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PendingFees {}).unwrap()).unwrap();
+        res.fees
+            .into_iter()
+            .map(|fee| (fee.address.to_string(), fee.amount.u128()))
+            .collect::<Vec<_>>()
+    };
+
+    // the admin takes the treasury fee until the recipients are set
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    assert_eq!(pending_fees(&deps), vec![("admin".to_string(), 20)]);
+
+    let err = update_fee_recipients(&mut deps, (0..11).map(|_| ("treasury", "0.01", "treasury")).collect()).unwrap_err();
+    assert_eq!(err, ContractError::TooManyFeeRecipients(10));
+    let err = update_fee_recipients(&mut deps, vec![("treasury", "0.1", "treasury"), ("insurance", "0.1", "treasury")]).unwrap_err();
+    assert_eq!(err, ContractError::DuplicateFeeLabel("treasury".to_string()));
+    let err = update_fee_recipients(&mut deps, vec![("treasury", "0.2", "treasury"), ("insurance", "0.15", "insurance")]).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFee {});
+
+    // a third party joins without a new contract, the admin keeps what it took
+    update_fee_recipients(
+        &mut deps,
+        vec![("treasury", "0.1", "treasury"), ("insurance", "0.05", "insurance"), ("syne_staking", "0.1", "syne_staking")],
+    )
+    .unwrap();
+    receive(&mut deps, "converter", 1000, Cw20HookMsg::DistributeRewards { address: None });
+    assert_eq!(
+        pending_fees(&deps),
+        vec![
+            ("admin".to_string(), 20),
+            ("insurance".to_string(), 50),
+            ("syne_staking".to_string(), 100),
+            ("treasury".to_string(), 100),
+        ]
+    );
+    let reward: RewardResponse = from_binary(
+        &query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: "alice".to_string() }).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(reward.rewards, Uint128::new(80 + 750));
+
+    // the rewards waiting in the converter show at the current shares
+    set_pending_rewards(&mut deps, 200);
+    assert_eq!(
+        pending_fees(&deps),
+        vec![
+            ("admin".to_string(), 20),
+            ("insurance".to_string(), 60),
+            ("syne_staking".to_string(), 120),
+            ("treasury".to_string(), 120),
+        ]
+    );
+
+    // anyone pays everyone
+    set_pending_rewards(&mut deps, 0);
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::FlushFees {}).unwrap();
+    let transfer = |recipient: &str, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) }).unwrap(),
+            funds: vec![],
+        }))
+    };
+    assert_eq!(
+        res.messages,
+        vec![transfer("admin", 20), transfer("insurance", 50), transfer("syne_staking", 100), transfer("treasury", 100)]
+    );
+    assert_eq!(pending_fees(&deps), vec![]);
+    let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::FlushFees {}).unwrap_err();
+    assert_eq!(err, ContractError::NoWithdrawable {});
+
+    let stats: StatsResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap()).unwrap();
+    assert_eq!(stats.total_fees, Uint128::new(270));
+}
+
+#[test]
+fn paused_claims_halt_reward_payouts() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    let set_paused = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, paused: bool| {
+        let operations = vec![PausableOperation::Claim];
+        let msg = if paused { ExecuteMsg::Pause { operations } } else { ExecuteMsg::Unpause { operations } };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    };
+    let distribute = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+        execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), ExecuteMsg::DistributeRewards { address: Addr::unchecked("alice") })
+    };
+
+    // anyone forcing a payout is refused, rewards sent along only accrue
+    set_paused(&mut deps, true);
+    let err = distribute(&mut deps).unwrap_err();
+    assert_eq!(err, ContractError::Pausable(PausableError::Paused(PausableOperation::Claim)));
+    let res = receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: Some(Addr::unchecked("alice")) });
+    assert!(res.messages.is_empty());
+    let reward: RewardResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: "alice".to_string() }).unwrap()).unwrap();
+    assert_eq!(reward.rewards, Uint128::new(80));
+
+    set_paused(&mut deps, false);
+    let res = distribute(&mut deps).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: "alice".to_string(), amount: Uint128::new(80) }).unwrap(),
+            funds: vec![],
+        }))]
+    );
+}
+
+#[test]
+fn paused_withdrawals_halt_share_transfers() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::IncreaseAllowance { spender: "pool".to_string(), amount: Uint128::new(400), expires: None },
+    )
+    .unwrap();
+    let set_paused = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, paused: bool| {
+        let operations = vec![PausableOperation::Withdraw];
+        let msg = if paused { ExecuteMsg::Pause { operations } } else { ExecuteMsg::Unpause { operations } };
+        execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    };
+    let transfers = [
+        ("alice", ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::new(100) }),
+        ("alice", ExecuteMsg::Send { contract: "pool".to_string(), amount: Uint128::new(100), msg: to_binary("provide").unwrap() }),
+        ("pool", ExecuteMsg::TransferFrom { owner: "alice".to_string(), recipient: "bob".to_string(), amount: Uint128::new(100) }),
+        (
+            "pool",
+            ExecuteMsg::SendFrom { owner: "alice".to_string(), contract: "pool".to_string(), amount: Uint128::new(100), msg: to_binary("provide").unwrap() },
+        ),
+    ];
+
+    set_paused(&mut deps, true);
+    for (sender, msg) in transfers.clone() {
+        let err = execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Pausable(PausableError::Paused(PausableOperation::Withdraw)));
+    }
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::new(1000));
+
+    set_paused(&mut deps, false);
+    for (sender, msg) in transfers {
+        execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), msg).unwrap();
+    }
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::new(600));
+}
+
+#[test]
+fn share_token() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let balance = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str| {
+        let res: BalanceResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Balance { address: address.to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.balance.u128()
+    };
+    let reward = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str| {
+        let res: RewardResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: address.to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.rewards.u128()
+    };
+    let transfer = |recipient: &str, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) }).unwrap(),
+            funds: vec![],
+        }))
+    };
+
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    let info: TokenInfoResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TokenInfo {}).unwrap()).unwrap();
+    assert_eq!(
+        info,
+        TokenInfoResponse {
+            name: "Staked bLOOP".to_string(),
+            symbol: "sbLOOP".to_string(),
+            decimals: 6,
+            total_supply: Uint128::new(1000),
+        }
+    );
+    assert_eq!(balance(&deps, "alice"), 1000);
+
+    // the rewards earned so far stay with alice, the ones to come are shared
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::new(400) },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer("alice", 80)]);
+    assert_eq!(balance(&deps, "alice"), 600);
+    assert_eq!(balance(&deps, "bob"), 400);
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    assert_eq!(reward(&deps, "alice"), 48);
+    assert_eq!(reward(&deps, "bob"), 32);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::Transfer { recipient: "carol".to_string(), amount: Uint128::new(401) },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidAmount {});
+
+    // a pool pulls the shares it was allowed and is told about a send
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::IncreaseAllowance { spender: "pool".to_string(), amount: Uint128::new(200), expires: None },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pool", &[]),
+        ExecuteMsg::TransferFrom { owner: "alice".to_string(), recipient: "pool".to_string(), amount: Uint128::new(150) },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer("alice", 48)]);
+    assert_eq!(balance(&deps, "pool"), 150);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("pool", &[]),
+        ExecuteMsg::TransferFrom { owner: "alice".to_string(), recipient: "pool".to_string(), amount: Uint128::new(100) },
+    )
+    .unwrap_err();
+    let allowance: AllowanceResponse = from_binary(
+        &query(deps.as_ref(), mock_env(), QueryMsg::Allowance { owner: "alice".to_string(), spender: "pool".to_string() }).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(allowance.allowance, Uint128::new(50));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::Send { contract: "pool".to_string(), amount: Uint128::new(400), msg: to_binary("provide").unwrap() },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer("bob", 32),
+            SubMsg::new(
+                Cw20ReceiveMsg {
+                    sender: "bob".to_string(),
+                    amount: Uint128::new(400),
+                    msg: to_binary("provide").unwrap(),
+                }
+                .into_cosmos_msg("pool")
+                .unwrap()
+            ),
+        ]
+    );
+    assert_eq!(balance(&deps, "bob"), 0);
+    assert_eq!(balance(&deps, "pool"), 550);
+    let info: TokenInfoResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TokenInfo {}).unwrap()).unwrap();
+    assert_eq!(info.total_supply, Uint128::new(1000));
+}
+
+#[test]
+fn large_supplies_keep_dust_in_the_vault() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let reward = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str| {
+        let res: RewardResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Reward { address: address.to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.rewards.u128()
+    };
+    let withdraw = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, address: &str| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(address, &[]),
+            ExecuteMsg::WithdrawRewards { owner: None, receiver: None },
+        )
+        .unwrap()
+    };
+    let transfer = |recipient: &str, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) }).unwrap(),
+            funds: vec![],
+        }))
+    };
+
+    let whale = 10u128.pow(30);
+    receive(&mut deps, "whale", whale, Cw20HookMsg::Stake {});
+    receive(&mut deps, "minnow", 3, Cw20HookMsg::Stake {});
+
+    // 20% fee, then 0.8 per share rounded down, the 4 left over are nobody's
+    let distributed = whale + 7;
+    receive(&mut deps, "converter", distributed, Cw20HookMsg::DistributeRewards { address: None });
+    let total: TotalStakedResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalStake {}).unwrap()).unwrap();
+    assert_eq!(total.power, Decimal256::percent(80));
+    let fees: PendingFeesResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::PendingFees {}).unwrap()).unwrap();
+    let fee = fees.fees[0].amount.u128();
+    assert_eq!(fee, whale / 5 + 1);
+    assert_eq!(reward(&deps, "whale"), whale / 10 * 8);
+    assert_eq!(reward(&deps, "minnow"), 2);
+    assert_eq!(distributed - fee - whale / 10 * 8 - 2, 4);
+
+    assert_eq!(withdraw(&mut deps, "whale").messages, vec![transfer("whale", whale / 10 * 8)]);
+    assert_eq!(withdraw(&mut deps, "minnow").messages, vec![transfer("minnow", 2)]);
+    assert_eq!(reward(&deps, "whale"), 0);
+    assert_eq!(reward(&deps, "minnow"), 0);
+
+    // too little to move the index, the fee is still taken and the rest stays put
+    receive(&mut deps, "converter", 1_000_000, Cw20HookMsg::DistributeRewards { address: None });
+    let total: TotalStakedResponse = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalStake {}).unwrap()).unwrap();
+    assert_eq!(total.power, Decimal256::percent(80));
+    assert_eq!(reward(&deps, "whale"), 0);
+    assert_eq!(reward(&deps, "minnow"), 0);
+
+    // the accounting errors instead of wrapping or panicking
+    let err = remove_stake(deps.as_mut().storage, &mock_env(), Addr::unchecked("minnow"), Uint128::new(4)).unwrap_err();
+    assert_eq!(err, ContractError::StakeUnderflow { stake: Uint128::new(3), amount: Uint128::new(4) });
+    let mut total = TOTAL_STAKED.load(deps.as_ref().storage).unwrap();
+    total.power = Decimal256::MAX;
+    TOTAL_STAKED.save(deps.as_mut().storage, &total, mock_env().block.height).unwrap();
+    let err = update_total_rewards(deps.as_mut().storage, &mock_env(), Uint128::new(whale)).unwrap_err();
+    assert!(matches!(err, ContractError::RewardOverflow(_)));
+    assert_eq!(query_staked(deps.as_ref(), "minnow".to_string()).unwrap().stake, Uint128::new(3));
+}
+
+#[test]
+fn reward_streams_on_large_supplies() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let env = mock_env();
+    execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::AddRewardStream { asset: token_asset_info("partner") }).unwrap();
+    let fund = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, amount: u128| {
+        receive_at(deps, mock_env(), "partner", "partner_wallet", amount, Cw20HookMsg::FundRewards { duration: None })
+    };
+
+    // one share takes more per share than a `Decimal` holds
+    receive(&mut deps, "minnow", 1, Cw20HookMsg::Stake {});
+    let flood = 10u128.pow(38);
+    fund(&mut deps, flood).unwrap();
+    assert_eq!(stream_rewards(&deps, &env, "minnow"), vec![("partner".to_string(), flood)]);
+
+    // the whale moves the index by its last digit, the 7 left over are nobody's
+    let whale = 10u128.pow(30);
+    receive(&mut deps, "whale", whale, Cw20HookMsg::Stake {});
+    fund(&mut deps, whale / 10u128.pow(18) + 7).unwrap();
+    assert_eq!(stream_rewards(&deps, &env, "whale"), vec![("partner".to_string(), whale / 10u128.pow(18))]);
+    assert_eq!(stream_rewards(&deps, &env, "minnow"), vec![("partner".to_string(), flood)]);
+
+    // the index errors instead of wrapping or panicking
+    let asset = AssetInfoValidated::Token(Addr::unchecked("partner"));
+    let mut stream = REWARD_STREAMS.load(deps.as_ref().storage, &asset).unwrap();
+    stream.power = Decimal256::MAX;
+    REWARD_STREAMS.save(deps.as_mut().storage, &asset, &stream).unwrap();
+    let err = fund(&mut deps, whale).unwrap_err();
+    assert!(matches!(err, ContractError::RewardOverflow(_)));
+}
+
+#[test]
+fn reward_rate() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let at = |seconds: u64| {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    };
+    let distribute = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, env: Env| {
+        receive_at(deps, env, "bloop", "converter", 1000, Cw20HookMsg::DistributeRewards { address: None }).unwrap();
+    };
+    let rate = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env: Env, window: RewardWindow| {
+        let res: RewardRateResponse = from_binary(&query(deps.as_ref(), env, QueryMsg::RewardRate { window }).unwrap()).unwrap();
+        (res.rate, res.distributed.u128(), res.since)
+    };
+    let start = mock_env().block.time.seconds();
+    const HOUR: u64 = 60 * 60;
+
+    assert_eq!(rate(&deps, at(0), RewardWindow::Day), (Decimal256::zero(), 0, start));
+
+    // 800 of every 1000 go to the 1000 staked, the first distribution starts the history
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    distribute(&mut deps, at(0));
+    distribute(&mut deps, at(12 * HOUR));
+    assert_eq!(
+        rate(&deps, at(12 * HOUR), RewardWindow::Day),
+        (Decimal256::new(Uint256::from(800_000_000_000_000_000u128 / (12 * HOUR) as u128)), 800, start)
+    );
+
+    // the day is measured from the last point before it, the week from the start of the history
+    distribute(&mut deps, at(36 * HOUR));
+    distribute(&mut deps, at(48 * HOUR));
+    assert_eq!(
+        rate(&deps, at(48 * HOUR), RewardWindow::Day),
+        (Decimal256::new(Uint256::from(1_600_000_000_000_000_000u128 / (36 * HOUR) as u128)), 1600, start + 12 * HOUR)
+    );
+    let (week_rate, distributed, since) = rate(&deps, at(48 * HOUR), RewardWindow::Week);
+    assert_eq!(
+        (week_rate, distributed, since),
+        (Decimal256::new(Uint256::from(2_400_000_000_000_000_000u128 / (48 * HOUR) as u128)), 2400, start)
+    );
+    assert_eq!(rate(&deps, at(48 * HOUR), RewardWindow::Month), (week_rate, 2400, start));
+
+    // over a year, the rate gives the APR
+    let apr = week_rate * Decimal256::from_atomics(365 * 24 * HOUR, 0).unwrap();
+    assert_eq!(apr, Decimal256::from_str("437.999999999971968").unwrap());
+}
+
+#[test]
+fn delegated_withdrawal() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let withdraw = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, sender: &str, owner: Option<&str>, receiver: Option<&str>| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(sender, &[]),
+            ExecuteMsg::WithdrawRewards { owner: owner.map(str::to_string), receiver: receiver.map(str::to_string) },
+        )
+    };
+    let delegate = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, delegated: &str| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::DelegateWithdrawal { delegated: delegated.to_string() },
+        )
+        .unwrap();
+        let res: DelegatedWithdrawalResponse = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::DelegatedWithdrawal { owner: "alice".to_string() }).unwrap(),
+        )
+        .unwrap();
+        res.delegated
+    };
+    let transfer = |recipient: &str, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: recipient.to_string(), amount: Uint128::new(amount) }).unwrap(),
+            funds: vec![],
+        }))
+    };
+
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "bob", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+
+    // only alice withdraws her rewards until she delegates
+    let err = withdraw(&mut deps, "treasury", Some("alice"), None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    assert_eq!(delegate(&mut deps, "treasury"), Some(Addr::unchecked("treasury")));
+    let res = withdraw(&mut deps, "treasury", Some("alice"), Some("sub_account")).unwrap();
+    assert_eq!(res.messages, vec![transfer("sub_account", 40)]);
+    // the delegate is paid itself by default, and cannot withdraw for anyone else
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let res = withdraw(&mut deps, "treasury", Some("alice"), None).unwrap();
+    assert_eq!(res.messages, vec![transfer("treasury", 40)]);
+    let err = withdraw(&mut deps, "treasury", Some("bob"), None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the owner keeps withdrawing, anywhere
+    let res = withdraw(&mut deps, "bob", None, Some("cold_wallet")).unwrap();
+    assert_eq!(res.messages, vec![transfer("cold_wallet", 80)]);
+
+    // delegating to herself removes the delegate
+    assert_eq!(delegate(&mut deps, "alice"), None);
+    let err = withdraw(&mut deps, "treasury", Some("alice"), None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn withdraw_rewards_as() {
+    let mut deps = mock_dependencies();
+    instantiate_vault(&mut deps);
+    set_pending_rewards(&mut deps, 0);
+    let withdraw_as = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, operations: Option<Vec<SwapOperation>>| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            ExecuteMsg::WithdrawRewardsAs {
+                ask_asset: native_asset_info("ujuno"),
+                max_spread: Some(Decimal::percent(1)),
+                operations,
+                minimum_receive: Uint128::new(70),
+            },
+        )
+    };
+    let hop = |offer: AssetInfo, ask: AssetInfo| SwapOperation::SynedexSwap { offer_asset_info: offer, ask_asset_info: ask };
+    let swap = |operations: Vec<SwapOperation>, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "bloop".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "router".to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&MultiHopCw20HookMsg::ExecuteSwapOperations {
+                    operations,
+                    minimum_receive: Some(Uint128::new(70)),
+                    receiver: Some("alice".to_string()),
+                    max_spread: Some(Decimal::percent(1)),
+                    referral_address: None,
+                    referral_commission: None,
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    };
+
+    receive(&mut deps, "alice", 1000, Cw20HookMsg::Stake {});
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+
+    let err = withdraw_as(&mut deps, None).unwrap_err();
+    assert_eq!(err, ContractError::NoSwapRouter {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        ExecuteMsg::UpdateConfig { min_bond: None, fee_recipients: None, unbonding_period: None, swap_router: Some("router".to_string()) },
+    )
+    .unwrap();
+
+    // the route has to take the staked token to the asked asset
+    let err = withdraw_as(&mut deps, Some(vec![])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSwapRoute {});
+    let err = withdraw_as(&mut deps, Some(vec![hop(native_asset_info("uusdc"), native_asset_info("ujuno"))])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSwapRoute {});
+    let err = withdraw_as(&mut deps, Some(vec![hop(token_asset_info("bloop"), native_asset_info("uusdc"))])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSwapRoute {});
+
+    // straight through the pair by default, the router checks the minimum
+    let res = withdraw_as(&mut deps, None).unwrap();
+    assert_eq!(res.messages, vec![swap(vec![hop(token_asset_info("bloop"), native_asset_info("ujuno"))], 80)]);
+
+    // or over several pairs, nothing is swapped without rewards
+    let route = vec![
+        hop(token_asset_info("bloop"), native_asset_info("uusdc")),
+        hop(native_asset_info("uusdc"), native_asset_info("ujuno")),
+    ];
+    assert!(withdraw_as(&mut deps, Some(route.clone())).unwrap().messages.is_empty());
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let res = withdraw_as(&mut deps, Some(route.clone())).unwrap();
+    assert_eq!(res.messages, vec![swap(route.clone(), 80)]);
+
+    // auto-compounding alice still gets the swap she asked for
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::SetAutoCompound { enabled: true }).unwrap();
+    receive(&mut deps, "converter", 100, Cw20HookMsg::DistributeRewards { address: None });
+    let res = withdraw_as(&mut deps, Some(route.clone())).unwrap();
+    assert_eq!(res.messages, vec![swap(route, 80)]);
+    assert_eq!(query_staked(deps.as_ref(), "alice".to_string()).unwrap().stake, Uint128::new(1000));
+}
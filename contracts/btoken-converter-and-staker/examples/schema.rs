@@ -3,7 +3,7 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use bwynd_converter_and_staker::{msg::{ExecuteMsg, InstantiateMsg, QueryMsg, MigrateMsg}, state::Config};
+use btoken_converter_and_staker::{msg::{ExecuteMsg, InstantiateMsg, QueryMsg, MigrateMsg}, state::Config};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -0,0 +1,154 @@
+use cosmwasm_std::{Addr, Binary, CosmosMsg, Decimal, QuerierWrapper, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::state::{MultipleChoiceVote, Vote};
+
+mod loop_protocol;
+mod wynd;
+
+pub use loop_protocol::LoopStaking;
+pub use wynd::WyndStaking;
+
+/// Everything the converter needs to know about the protocol the underlying token is staked in.
+pub trait UnderlyingStaking {
+    /// Name and symbol of the bToken minted for the underlying token
+    fn btoken_name(&self) -> (String, String);
+
+    /// The underlying cw20 token accepted by `Convert`
+    fn token(&self) -> &Addr;
+
+    /// The contract holding the stake and paying the rewards
+    fn staking_contract(&self) -> &Addr;
+
+    /// Unbonding periods (WYND) or staking durations (LOOP) new stakes are spread across
+    fn unbonding_tiers(&self) -> Vec<UnbondingTier>;
+
+    fn set_unbonding_tiers(&mut self, tiers: Vec<UnbondingTier>) -> Result<(), ContractError>;
+
+    /// Messages staking freshly deposited underlying tokens
+    fn stake_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError>;
+
+    /// Rewards of `staker` that are ready to be compounded
+    fn pending_rewards(&self, querier: QuerierWrapper, staker: &Addr) -> StdResult<Uint128>;
+
+    /// Amount `staker` has staked in the protocol across the given unbonding periods
+    fn delegated(&self, querier: QuerierWrapper, staker: &Addr, periods: &[u64]) -> StdResult<Uint128>;
+
+    /// Messages withdrawing the pending rewards and staking them again
+    fn compound_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError>;
+
+    /// Whether `stake_msgs` already compounds the pending rewards
+    fn stake_compounds_rewards(&self) -> bool;
+
+    /// Messages starting the unbonding of redeemed tokens
+    fn unbond_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError>;
+
+    /// Messages moving the unbonded tokens back to the converter
+    fn claim_msgs(&self) -> Result<Vec<CosmosMsg>, ContractError>;
+
+    /// Forwards the vote of the synergistic gauge to the protocol gauge
+    fn vote_msg(&self, gauge_contract: &str, vote: GaugeVote) -> Result<CosmosMsg, ContractError>;
+
+    /// Instantiate message of the bToken vault
+    fn vault_instantiate_msg(&self, admin: &Addr, btoken: &Addr, min_bond: Uint128) -> StdResult<Binary>;
+}
+
+/// Share of new stakes locked for one unbonding period.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct UnbondingTier {
+    /// Unbonding period in seconds
+    pub unbonding_period: u64,
+    pub weight: Decimal,
+}
+
+/// Amount staked or unbonded for one unbonding period.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct Delegation {
+    pub unbonding_period: u64,
+    pub amount: Uint128,
+}
+
+/// Checks the tiers are not empty, have distinct non zero periods and weights summing up to 1.
+pub fn validate_unbonding_tiers(tiers: &[UnbondingTier]) -> Result<(), ContractError> {
+    if tiers.is_empty() {
+        return Err(ContractError::InvalidUnbondingTiers {});
+    }
+
+    let mut total_weight = Decimal::zero();
+    for (i, tier) in tiers.iter().enumerate() {
+        if tier.unbonding_period == 0 {
+            return Err(ContractError::UnbondingPeriodErr {});
+        }
+        if tier.weight.is_zero()
+            || tiers[..i].iter().any(|t| t.unbonding_period == tier.unbonding_period)
+        {
+            return Err(ContractError::InvalidUnbondingTiers {});
+        }
+        total_weight = total_weight.checked_add(tier.weight).map_err(|_| ContractError::InvalidUnbondingTiers {})?;
+    }
+
+    if total_weight != Decimal::one() {
+        return Err(ContractError::InvalidUnbondingTiers {});
+    }
+
+    Ok(())
+}
+
+/// Spreads `amount` across the tiers by weight. Rounding dust goes to the first tier.
+pub fn split_by_weight(tiers: &[UnbondingTier], amount: Uint128) -> Vec<Delegation> {
+    let mut delegations: Vec<Delegation> = tiers
+        .iter()
+        .map(|tier| Delegation {
+            unbonding_period: tier.unbonding_period,
+            amount: amount * tier.weight,
+        })
+        .collect();
+
+    let split: Uint128 = delegations.iter().map(|d| d.amount).sum();
+    if let Some(first) = delegations.first_mut() {
+        first.amount += amount - split;
+    }
+
+    delegations.retain(|d| !d.amount.is_zero());
+    delegations
+}
+
+/// The protocol chosen at instantiation together with its settings.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnderlyingProtocol {
+    Wynd(WyndStaking),
+    Loop(LoopStaking),
+}
+
+impl UnderlyingProtocol {
+    pub fn adapter(&self) -> &dyn UnderlyingStaking {
+        match self {
+            UnderlyingProtocol::Wynd(wynd) => wynd,
+            UnderlyingProtocol::Loop(loop_staking) => loop_staking,
+        }
+    }
+
+    pub fn adapter_mut(&mut self) -> &mut dyn UnderlyingStaking {
+        match self {
+            UnderlyingProtocol::Wynd(wynd) => wynd,
+            UnderlyingProtocol::Loop(loop_staking) => loop_staking,
+        }
+    }
+}
+
+/// A vote cast by the synergistic gauge, in the format of the protocol gauge.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GaugeVote {
+    PlaceVotes {
+        gauge: u64,
+        votes: Option<Vec<Vote>>,
+    },
+    Vote {
+        proposal_id: u64,
+        vote: Vec<MultipleChoiceVote>,
+    },
+}
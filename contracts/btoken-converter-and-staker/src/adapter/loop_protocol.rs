@@ -0,0 +1,137 @@
+use cosmwasm_std::{to_binary, Addr, Binary, CosmosMsg, Decimal, QuerierWrapper, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use synedao::{
+    bloop_vault::InstantiateMsg as VaultInstantiateMsg,
+    loop_protocol_staking::ExecuteMsg as LoopProtocolStakingExecuteMsg,
+};
+
+use crate::adapter::{Delegation, GaugeVote, UnbondingTier, UnderlyingStaking};
+use crate::error::ContractError;
+use crate::queriers::{query_loop_protocol_staked, query_loop_protocol_staking_rewards};
+use crate::state::MultipleChoiceVote;
+
+/// LOOP is sent to the LOOP protocol staking contract for a fixed duration.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct LoopStaking {
+    pub loop_token: Addr,
+    pub loop_protocol_staking: Addr,
+    pub duration: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum LoopGaugeExecuteMsg {
+    Vote {
+        proposal_id: u64,
+        vote: Vec<MultipleChoiceVote>,
+    },
+}
+
+impl UnderlyingStaking for LoopStaking {
+    fn btoken_name(&self) -> (String, String) {
+        ("bLOOP Token".to_string(), "bLOOP".to_string())
+    }
+
+    fn token(&self) -> &Addr {
+        &self.loop_token
+    }
+
+    fn staking_contract(&self) -> &Addr {
+        &self.loop_protocol_staking
+    }
+
+    fn unbonding_tiers(&self) -> Vec<UnbondingTier> {
+        vec![UnbondingTier { unbonding_period: self.duration, weight: Decimal::one() }]
+    }
+
+    // LOOP is staked for a single duration only
+    fn set_unbonding_tiers(&mut self, tiers: Vec<UnbondingTier>) -> Result<(), ContractError> {
+        match tiers.as_slice() {
+            [tier] if tier.weight == Decimal::one() => {
+                self.duration = tier.unbonding_period;
+                Ok(())
+            },
+            _ => Err(ContractError::InvalidUnbondingTiers {}),
+        }
+    }
+
+    fn stake_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError> {
+        delegations
+            .iter()
+            .map(|delegation| {
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: self.loop_token.to_string(),
+                    msg: to_binary(
+                        &Cw20ExecuteMsg::Send {
+                            contract: self.loop_protocol_staking.to_string(),
+                            amount: delegation.amount,
+                            msg: to_binary(
+                                &LoopProtocolStakingExecuteMsg::Stake {
+                                    duration: delegation.unbonding_period,
+                                }
+                            )?
+                        }
+                    )?,
+                    funds: vec![],
+                }))
+            })
+            .collect()
+    }
+
+    fn pending_rewards(&self, querier: QuerierWrapper, staker: &Addr) -> StdResult<Uint128> {
+        let user_reward_reponse = query_loop_protocol_staking_rewards(querier, self.loop_protocol_staking.clone(), staker.clone(), self.duration)?;
+        Ok(user_reward_reponse.user_reward.checked_add(user_reward_reponse.pending_reward)?)
+    }
+
+    fn delegated(&self, querier: QuerierWrapper, staker: &Addr, periods: &[u64]) -> StdResult<Uint128> {
+        periods.iter().try_fold(Uint128::zero(), |total, period| {
+            Ok(total + query_loop_protocol_staked(querier, self.loop_protocol_staking.clone(), staker.clone(), *period)?)
+        })
+    }
+
+    fn compound_msgs(&self, _delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError> {
+        Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.loop_protocol_staking.to_string(),
+            msg: to_binary(&LoopProtocolStakingExecuteMsg::Restake {
+                duration: self.duration,
+            })?,
+            funds: vec![],
+        })])
+    }
+
+    // staking more LOOP restakes the pending rewards as well
+    fn stake_compounds_rewards(&self) -> bool {
+        true
+    }
+
+    fn unbond_msgs(&self, _delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError> {
+        Err(ContractError::RedeemNotSupported {})
+    }
+
+    fn claim_msgs(&self) -> Result<Vec<CosmosMsg>, ContractError> {
+        Err(ContractError::RedeemNotSupported {})
+    }
+
+    fn vote_msg(&self, gauge_contract: &str, vote: GaugeVote) -> Result<CosmosMsg, ContractError> {
+        match vote {
+            GaugeVote::Vote { proposal_id, vote } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: gauge_contract.to_string(),
+                msg: to_binary(&LoopGaugeExecuteMsg::Vote { proposal_id, vote })?,
+                funds: vec![],
+            })),
+            GaugeVote::PlaceVotes { .. } => Err(ContractError::InvalidVote {}),
+        }
+    }
+
+    fn vault_instantiate_msg(&self, admin: &Addr, btoken: &Addr, min_bond: Uint128) -> StdResult<Binary> {
+        to_binary(&VaultInstantiateMsg {
+            admin: admin.to_string(),
+            token: btoken.to_string(),
+            loop_protocol_staking: self.loop_protocol_staking.to_string(),
+            min_bond,
+        })
+    }
+}
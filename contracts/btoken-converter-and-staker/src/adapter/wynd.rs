@@ -0,0 +1,162 @@
+use cosmwasm_std::{to_binary, Addr, Binary, CosmosMsg, QuerierWrapper, StdResult, Uint128, WasmMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use synedao::{
+    bwynd_vault::InstantiateMsg as VaultInstantiateMsg,
+    cw20_vesting::ExecuteMsg as Cw20VestingExecuteMsg,
+    wynd_dao_core::ExecuteMsg as WyndDaoCoreExecuteMsg,
+};
+
+use crate::adapter::{validate_unbonding_tiers, Delegation, GaugeVote, UnbondingTier, UnderlyingStaking};
+use crate::error::ContractError;
+use crate::msg::ReceiveDelegationMsg;
+use crate::queriers::{query_wynd_dao_core_rewards, query_wynd_dao_core_staked};
+use crate::state::Vote;
+
+/// WYND is delegated from the WYND token to the WYND staking module.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct WyndStaking {
+    pub wynd_token: Addr,
+    pub wynd_staking_module: Addr,
+    /// WYND staking tiers new delegations are spread across
+    pub unbonding_periods: Vec<UnbondingTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum WyndGaugeExecuteMsg {
+    PlaceVotes {
+        gauge: u64,
+        votes: Option<Vec<Vote>>,
+    },
+}
+
+impl WyndStaking {
+    fn delegate_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError> {
+        delegations
+            .iter()
+            .map(|delegation| {
+                if delegation.unbonding_period == 0 {
+                    return Err(ContractError::UnbondingPeriodErr {});
+                }
+
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: self.wynd_token.to_string(),
+                    msg: to_binary(
+                        &Cw20VestingExecuteMsg::Delegate {
+                            amount: delegation.amount,
+                            msg: to_binary(
+                                &ReceiveDelegationMsg::Delegate {
+                                    unbonding_period: delegation.unbonding_period,
+                                }
+                            )?
+                        }
+                    )?,
+                    funds: vec![],
+                }))
+            })
+            .collect()
+    }
+}
+
+impl UnderlyingStaking for WyndStaking {
+    fn btoken_name(&self) -> (String, String) {
+        ("bWYND Token".to_string(), "bWYND".to_string())
+    }
+
+    fn token(&self) -> &Addr {
+        &self.wynd_token
+    }
+
+    fn staking_contract(&self) -> &Addr {
+        &self.wynd_staking_module
+    }
+
+    fn unbonding_tiers(&self) -> Vec<UnbondingTier> {
+        self.unbonding_periods.clone()
+    }
+
+    fn set_unbonding_tiers(&mut self, tiers: Vec<UnbondingTier>) -> Result<(), ContractError> {
+        validate_unbonding_tiers(&tiers)?;
+        self.unbonding_periods = tiers;
+        Ok(())
+    }
+
+    fn stake_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError> {
+        self.delegate_msgs(delegations)
+    }
+
+    fn pending_rewards(&self, querier: QuerierWrapper, staker: &Addr) -> StdResult<Uint128> {
+        Ok(query_wynd_dao_core_rewards(querier, self.wynd_staking_module.clone(), staker.clone())?.rewards)
+    }
+
+    fn delegated(&self, querier: QuerierWrapper, staker: &Addr, periods: &[u64]) -> StdResult<Uint128> {
+        periods.iter().try_fold(Uint128::zero(), |total, period| {
+            Ok(total + query_wynd_dao_core_staked(querier, self.wynd_staking_module.clone(), staker.clone(), *period)?)
+        })
+    }
+
+    fn compound_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError> {
+        let mut messages = vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: self.wynd_staking_module.to_string(),
+                msg: to_binary(&WyndDaoCoreExecuteMsg::WithdrawRewards {
+                    owner: None,
+                    receiver: None,
+                })?,
+                funds: vec![],
+            }),
+        ];
+        messages.extend(self.delegate_msgs(delegations)?);
+        Ok(messages)
+    }
+
+    fn stake_compounds_rewards(&self) -> bool {
+        false
+    }
+
+    fn unbond_msgs(&self, delegations: &[Delegation]) -> Result<Vec<CosmosMsg>, ContractError> {
+        delegations
+            .iter()
+            .map(|delegation| {
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: self.wynd_staking_module.to_string(),
+                    msg: to_binary(&WyndDaoCoreExecuteMsg::Unbond {
+                        tokens: delegation.amount,
+                        unbonding_period: delegation.unbonding_period,
+                    })?,
+                    funds: vec![],
+                }))
+            })
+            .collect()
+    }
+
+    fn claim_msgs(&self) -> Result<Vec<CosmosMsg>, ContractError> {
+        Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.wynd_staking_module.to_string(),
+            msg: to_binary(&WyndDaoCoreExecuteMsg::Claim {})?,
+            funds: vec![],
+        })])
+    }
+
+    fn vote_msg(&self, gauge_contract: &str, vote: GaugeVote) -> Result<CosmosMsg, ContractError> {
+        match vote {
+            GaugeVote::PlaceVotes { gauge, votes } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: gauge_contract.to_string(),
+                msg: to_binary(&WyndGaugeExecuteMsg::PlaceVotes { gauge, votes })?,
+                funds: vec![],
+            })),
+            GaugeVote::Vote { .. } => Err(ContractError::InvalidVote {}),
+        }
+    }
+
+    fn vault_instantiate_msg(&self, admin: &Addr, btoken: &Addr, min_bond: Uint128) -> StdResult<Binary> {
+        to_binary(&VaultInstantiateMsg {
+            admin: admin.to_string(),
+            token: btoken.to_string(),
+            wynd_staking_module: self.wynd_staking_module.to_string(),
+            min_bond,
+        })
+    }
+}
@@ -0,0 +1,1430 @@
+use std::collections::BTreeMap;
+
+use serde::de::IgnoredAny;
+
+use synedex::asset::{Asset, AssetInfo};
+use synedex::multi_hop::{
+    Cw20HookMsg as MultiHopCw20HookMsg, ExecuteMsg as MultiHopExecuteMsg, SwapOperation,
+};
+
+use crate::adapter::{split_by_weight, validate_unbonding_tiers, Delegation, GaugeVote, LoopStaking, UnbondingTier, UnderlyingProtocol, WyndStaking};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ConfigResponse, MigrateMsg, Cw20HookMsg, VaultCw20HookMsg, VaultExecuteMsg, GaugeConfigResponse, ProtocolMsg, DelegationsResponse, TierDelegation, TimelockConfigResponse, ScheduledActionsResponse, BackingRatioResponse, ExpectedBountyResponse};
+use crate::queriers::{query_token_balance, query_token_supply};
+use crate::state::{
+    Config, CONFIG, GaugeConfig, GAUGE_CONFIG, CLAIMS, DELEGATED, TimelockConfig, TIMELOCK_CONFIG,
+    AdminAction, AllowedMsg, ScheduledAction, SCHEDULED_ACTIONS, NEXT_ACTION_ID, BackingInfo, BACKING, PAUSABLE, OWNERSHIP_PROPOSAL
+};
+use crate::error::ContractError;
+use crate::v1;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, CosmosMsg, WasmMsg, from_binary, to_binary, ensure_eq, SubMsg, ReplyOn, Reply, StdError, Addr, Api, Decimal, Order, Storage, from_slice, coins,
+};
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
+use cw_controllers::{ClaimsResponse, PausableOperation};
+use cw_storage_plus::Bound;
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use cw_utils::{ensure_from_older_version, Duration, MsgInstantiateContractResponse};
+
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const INSTANTIATE_BTOKEN_ID: u64 = 1;
+const INSTANTIATE_VAULT_ID: u64 = 2;
+
+const MAX_KEEPER_BOUNTY_BPS: u64 = 1000;
+const BPS_DENOMINATOR: u64 = 10000;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+//Initialize the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    
+    let config = Config {
+        admin: deps.api.addr_validate(&msg.admin)?,
+        cw20_code_id: msg.cw20_code_id,
+        vault_code_id: msg.vault_code_id,
+        protocol: validate_protocol(deps.api, msg.protocol)?,
+        btoken: None,
+        btoken_vault: None,
+        keeper_bounty_bps: validate_keeper_bounty(msg.keeper_bounty_bps)?,
+        min_harvest: msg.min_harvest,
+        swap_router: msg.swap_router.map(|router| deps.api.addr_validate(&router)).transpose()?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    let gauge_config = GaugeConfig {
+        gauge_contract: msg.gauge_contract,
+        synergistic_gauge_contract: msg.synergistic_gauge_contract,
+    };
+
+    GAUGE_CONFIG.save(deps.storage, &gauge_config)?;
+
+    let timelock_config = TimelockConfig {
+        delay: msg.timelock_delay,
+        guardian: msg.guardian.map(|guardian| deps.api.addr_validate(&guardian)).transpose()?,
+        allowlist: vec![],
+    };
+
+    TIMELOCK_CONFIG.save(deps.storage, &timelock_config)?;
+    PAUSABLE.set_guardian(deps.storage, timelock_config.guardian)?;
+    NEXT_ACTION_ID.save(deps.storage, &1u64)?;
+    BACKING.save(deps.storage, &BackingInfo::default())?;
+
+    let (name, symbol) = config.protocol.adapter().btoken_name();
+
+    let sub_msg: Vec<SubMsg> = vec![SubMsg {
+        id: INSTANTIATE_BTOKEN_ID,
+        msg: WasmMsg::Instantiate {
+            admin: Some(config.admin.to_string()),
+            code_id: msg.cw20_code_id,
+            msg: to_binary(&Cw20InstantiateMsg {
+                name,
+                symbol: symbol.clone(),
+                decimals: 6,
+                initial_balances: [].into(),
+                mint: Some(MinterResponse {
+                    minter: env.contract.address.to_string(),
+                    cap: None
+                }),
+                marketing: None
+            })?,
+            funds: vec![],
+            label: format!("{} Contract", symbol),
+        }
+        .into(),
+        gas_limit: None,
+        reply_on: ReplyOn::Success,
+    }];
+
+    Ok(Response::new()
+        .add_submessages(sub_msg)
+    )
+}
+
+fn validate_keeper_bounty(keeper_bounty_bps: u64) -> Result<u64, ContractError> {
+    if keeper_bounty_bps > MAX_KEEPER_BOUNTY_BPS {
+        return Err(ContractError::KeeperBountyTooHigh(MAX_KEEPER_BOUNTY_BPS));
+    }
+    Ok(keeper_bounty_bps)
+}
+
+fn validate_protocol(api: &dyn Api, protocol: ProtocolMsg) -> Result<UnderlyingProtocol, ContractError> {
+    Ok(match protocol {
+        ProtocolMsg::Wynd {
+            wynd_token,
+            wynd_staking_module,
+            unbonding_periods
+        } => {
+            validate_unbonding_tiers(&unbonding_periods)?;
+            UnderlyingProtocol::Wynd(WyndStaking {
+                wynd_token: api.addr_validate(&wynd_token)?,
+                wynd_staking_module: api.addr_validate(&wynd_staking_module)?,
+                unbonding_periods,
+            })
+        },
+        ProtocolMsg::Loop {
+            loop_token,
+            loop_protocol_staking,
+            duration
+        } => UnderlyingProtocol::Loop(LoopStaking {
+            loop_token: api.addr_validate(&loop_token)?,
+            loop_protocol_staking: api.addr_validate(&loop_protocol_staking)?,
+            duration,
+        }),
+    })
+}
+
+//Execute the handle messages.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ProposeNewOwner {
+            owner,
+            expires_in,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_propose_new_owner(deps, info, &env, &config.admin, owner, expires_in)?)
+        },
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_drop_ownership_proposal(deps, info, &config.admin)?)
+        },
+        ExecuteMsg::ClaimOwnership {} => {
+            Ok(OWNERSHIP_PROPOSAL.execute_claim_ownership(deps, info, &env, |deps, admin| {
+                CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+                    config.admin = admin;
+                    Ok(config)
+                })?;
+                Ok(())
+            })?)
+        },
+        ExecuteMsg::UpdateConfig {
+            unbonding_periods,
+            keeper_bounty_bps,
+            min_harvest,
+            swap_router,
+        } => execute_update_config(
+            deps,
+            info,
+            unbonding_periods,
+            keeper_bounty_bps,
+            min_harvest,
+            swap_router,
+        ),
+        ExecuteMsg::UpdateGaugeConfig { 
+            gauge_contract, 
+            synergistic_gauge_contract 
+        } => execute_update_gauge_config(
+            deps, 
+            env,
+            info,
+            gauge_contract, 
+            synergistic_gauge_contract
+        ),
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::WithdrawRewards {} => execute_withdraw_rewards(deps, env, info),
+        ExecuteMsg::ClaimRedeemed {} => execute_claim_redeemed(deps, env, info),
+        ExecuteMsg::ZapConvert {
+            operations,
+            minimum_receive,
+            max_spread,
+        } => execute_zap_convert_native(deps, env, info, operations, minimum_receive, max_spread),
+        ExecuteMsg::ConvertSwapped {
+            recipient,
+            prev_balance,
+        } => execute_convert_swapped(deps, env, info, recipient, prev_balance),
+        ExecuteMsg::ScheduleAction { action } => execute_schedule_action(deps, env, info, action),
+        ExecuteMsg::ExecuteAction { id } => execute_action(deps, env, info, id),
+        ExecuteMsg::CancelAction { id } => execute_cancel_action(deps, env, info, id),
+        ExecuteMsg::PlaceVotes {
+            gauge,
+            votes,
+        } => execute_vote(deps, env, info.sender, GaugeVote::PlaceVotes { gauge, votes }),
+        ExecuteMsg::Vote {
+            proposal_id,
+            vote,
+        } => execute_vote(deps, env, info.sender, GaugeVote::Vote { proposal_id, vote }),
+        ExecuteMsg::Pause { operations } => {
+            let is_admin = info.sender == CONFIG.load(deps.storage)?.admin;
+            Ok(PAUSABLE.execute_pause(deps, info, is_admin, operations)?)
+        },
+        ExecuteMsg::Unpause { operations } => {
+            let is_admin = info.sender == CONFIG.load(deps.storage)?.admin;
+            Ok(PAUSABLE.execute_unpause(deps, info, is_admin, operations)?)
+        },
+        ExecuteMsg::UpdatePauseGuardian { guardian } => {
+            let is_admin = info.sender == CONFIG.load(deps.storage)?.admin;
+            let guardian = guardian.map(|guardian| deps.api.addr_validate(&guardian)).transpose()?;
+            Ok(PAUSABLE.execute_update_guardian(deps, info, is_admin, guardian)?)
+        },
+    }
+}
+
+// Only admin can execute it.
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    unbonding_periods: Option<Vec<UnbondingTier>>,
+    keeper_bounty_bps: Option<u64>,
+    min_harvest: Option<Uint128>,
+    swap_router: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    ensure_eq!(info.sender, config.admin, ContractError::Unauthorized {});
+
+    let mut changed = false;
+
+    let mut response = Response::new().add_attribute("action", "update_config");
+
+    if let Some(unbonding_periods) = unbonding_periods {
+        changed = true;
+        for tier in unbonding_periods.iter() {
+            response = response.add_attribute(
+                "unbonding_period",
+                format!("{}:{}", tier.unbonding_period, tier.weight),
+            );
+        }
+        config.protocol.adapter_mut().set_unbonding_tiers(unbonding_periods)?;
+    }
+
+    if let Some(keeper_bounty_bps) = keeper_bounty_bps {
+        changed = true;
+        config.keeper_bounty_bps = validate_keeper_bounty(keeper_bounty_bps)?;
+        response = response.add_attribute("keeper_bounty_bps", keeper_bounty_bps.to_string());
+    }
+
+    if let Some(min_harvest) = min_harvest {
+        changed = true;
+        config.min_harvest = min_harvest;
+        response = response.add_attribute("min_harvest", min_harvest.to_string());
+    }
+
+    if let Some(swap_router) = swap_router {
+        changed = true;
+        config.swap_router = Some(deps.api.addr_validate(&swap_router)?);
+        response = response.add_attribute("swap_router", swap_router);
+    }
+
+    ensure_eq!(changed, true, ContractError::InvalidParams {});
+    
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+pub fn execute_update_gauge_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    gauge_contract: Option<String>,
+    synergistic_gauge_contract: Option<String>,
+) -> Result<Response, ContractError> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let mut gauge_cfg = GAUGE_CONFIG.load(deps.storage)?;
+
+    ensure_eq!(info.sender, cfg.admin, ContractError::Unauthorized {});
+
+    let mut valid = false;
+
+    let mut res = Response::new()
+        .add_attribute("action", "update_gauge_config");
+
+    if let Some(gauge_contract) = gauge_contract {
+        gauge_cfg.gauge_contract = gauge_contract;
+        valid = true;
+        res = res.add_attribute(
+            "gauge_contract", 
+            &gauge_cfg.gauge_contract.to_string()
+        );
+    }
+
+    if synergistic_gauge_contract.is_some() {
+        gauge_cfg.synergistic_gauge_contract = synergistic_gauge_contract.clone();
+        valid = true;
+        res = res.add_attribute("synergistic_gauge_contract", &synergistic_gauge_contract.unwrap().to_string());
+    }
+
+    GAUGE_CONFIG.save(deps.storage, &gauge_cfg)?;
+    
+    ensure_eq!(valid, true, ContractError::InvalidParams {});
+
+    Ok(res)
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Convert {}) => execute_convert_token(
+            deps,
+            env,
+            info,
+            cw20_msg.sender,
+            cw20_msg.amount,
+        ),
+        Ok(Cw20HookMsg::Redeem {}) => execute_redeem(
+            deps,
+            env,
+            info,
+            cw20_msg.sender,
+            cw20_msg.amount,
+        ),
+        Ok(Cw20HookMsg::ZapConvert {
+            operations,
+            minimum_receive,
+            max_spread,
+        }) => execute_zap_convert(
+            deps,
+            env,
+            cw20_msg.sender,
+            Asset { info: AssetInfo::Token(info.sender.to_string()), amount: cw20_msg.amount },
+            operations,
+            minimum_receive,
+            max_spread,
+        ),
+        Err(_err) => Err(ContractError::GenericErr {}),
+    }
+}
+
+pub fn execute_convert_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+    amount: Uint128
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Convert)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure_eq!(&info.sender, config.protocol.adapter().token(), ContractError::InvalidToken {});
+
+    convert(deps, env, config, sender, amount)
+}
+
+// Stakes `amount` of underlying tokens the converter already holds and mints bTokens to `sender`.
+fn convert(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: String,
+    amount: Uint128
+) -> Result<Response, ContractError> {
+    let adapter = config.protocol.adapter();
+
+    ensure_eq!((amount.gt(&Uint128::zero())), true, ContractError::InvalidZeroAmount {});
+
+    let btoken = config.btoken.clone().unwrap().to_string();
+
+    let mut attrs = vec![
+        ("action", "stake_token".to_string()),
+        ("token", adapter.token().to_string()),
+        ("to", adapter.staking_contract().to_string()),
+        ("amount", amount.to_string()),
+        ("action", "mint_token".to_string()),
+        ("token", btoken.clone()),
+        ("to", sender.clone()),
+        ("amount", amount.to_string()),
+    ];
+    
+    let delegations = split_by_weight(&adapter.unbonding_tiers(), amount);
+    add_delegations(deps.storage, &delegations)?;
+    add_to_backing(deps.storage, |backing| &mut backing.deposited, amount)?;
+
+    let mut messages = adapter.stake_msgs(&delegations)?;
+    messages.push(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            //sending reward to user
+            contract_addr: btoken.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: sender.to_string(), amount })?,
+            funds: vec![],
+        })
+    );
+
+    let withdrawable_amount = adapter.pending_rewards(deps.querier, &env.contract.address)?;
+    if withdrawable_amount.gt(&Uint128::zero()) {
+        let reward_delegations = split_by_weight(&adapter.unbonding_tiers(), withdrawable_amount);
+        add_delegations(deps.storage, &reward_delegations)?;
+        add_to_backing(deps.storage, |backing| &mut backing.compounded, withdrawable_amount)?;
+
+        if !adapter.stake_compounds_rewards() {
+            attrs.push(("action", "restake_token".to_string()));
+            attrs.push(("token", adapter.token().to_string()));
+            attrs.push(("to", adapter.staking_contract().to_string()));
+            attrs.push(("amount", withdrawable_amount.to_string()));
+
+            messages.extend(adapter.compound_msgs(&reward_delegations)?);
+        }
+
+        attrs.push(("action", "mint_token".to_string()));
+        attrs.push(("token", btoken.clone()));
+        attrs.push(("to", env.contract.address.to_string()));
+        attrs.push(("amount", withdrawable_amount.to_string()));
+
+        attrs.push(("action", "distribute_rewards".to_string()));
+        attrs.push(("token", btoken.clone()));
+        attrs.push(("to", env.contract.address.to_string()));
+        attrs.push(("amount", withdrawable_amount.to_string()));
+
+        messages.push(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                //sending reward to user
+                contract_addr: btoken.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: env.contract.address.clone().to_string(), amount: withdrawable_amount })?,
+                funds: vec![],
+            })
+        );
+
+        messages.push(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: btoken,
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: config.btoken_vault.clone().unwrap().to_string(),
+                    amount: withdrawable_amount,
+                    msg: to_binary(&VaultCw20HookMsg::DistributeRewards { address: Some(deps.api.addr_validate(&sender)?) })?,
+                })?,
+                funds: vec![] 
+            })
+        );
+    } else {
+        messages.push(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.btoken_vault.clone().unwrap().to_string(),
+                msg: to_binary(&VaultExecuteMsg::DistributeRewards { address: deps.api.addr_validate(&sender)?, })?,
+                funds: vec![]
+            })
+        );
+    }
+
+    Ok(Response::new()
+        .add_attributes(attrs)
+        .add_messages(messages)
+    )
+}
+
+pub fn execute_zap_convert_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operations: Vec<SwapOperation>,
+    minimum_receive: Uint128,
+    max_spread: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let offer = match info.funds.as_slice() {
+        [coin] if !coin.amount.is_zero() => Asset {
+            info: AssetInfo::Native(coin.denom.clone()),
+            amount: coin.amount,
+        },
+        _ => return Err(ContractError::InvalidToken {}),
+    };
+
+    execute_zap_convert(deps, env, info.sender.to_string(), offer, operations, minimum_receive, max_spread)
+}
+
+// Swaps `offer` into the underlying token through the router, then converts what arrived.
+pub fn execute_zap_convert(
+    deps: DepsMut,
+    env: Env,
+    sender: String,
+    offer: Asset,
+    operations: Vec<SwapOperation>,
+    minimum_receive: Uint128,
+    max_spread: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Convert)?;
+    let config = CONFIG.load(deps.storage)?;
+    let adapter = config.protocol.adapter();
+
+    let swap_router = config.swap_router.clone().ok_or(ContractError::NoSwapRouter {})?;
+    ensure_eq!(offer.amount.is_zero(), false, ContractError::InvalidZeroAmount {});
+
+    let first_offer = match operations.first() {
+        Some(SwapOperation::SynedexSwap { offer_asset_info, .. }) => offer_asset_info.clone(),
+        None => return Err(ContractError::InvalidSwapRoute {}),
+    };
+    let last_ask = operations.last().map(|operation| operation.get_target_asset_info());
+    ensure_eq!(first_offer, offer.info, ContractError::InvalidSwapRoute {});
+    ensure_eq!(
+        last_ask,
+        Some(AssetInfo::Token(adapter.token().to_string())),
+        ContractError::InvalidSwapRoute {}
+    );
+
+    // tokens awaiting redemption claims are already in the balance
+    let prev_balance = query_token_balance(deps.querier, adapter.token().clone(), env.contract.address.clone())?;
+
+    let swap_msg = match &offer.info {
+        AssetInfo::Native(denom) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: swap_router.to_string(),
+            msg: to_binary(&MultiHopExecuteMsg::ExecuteSwapOperations {
+                operations,
+                minimum_receive: Some(minimum_receive),
+                receiver: None,
+                max_spread,
+                referral_address: None,
+                referral_commission: None,
+            })?,
+            funds: coins(offer.amount.u128(), denom),
+        }),
+        AssetInfo::Token(token) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: swap_router.to_string(),
+                amount: offer.amount,
+                msg: to_binary(&MultiHopCw20HookMsg::ExecuteSwapOperations {
+                    operations,
+                    minimum_receive: Some(minimum_receive),
+                    receiver: None,
+                    max_spread,
+                    referral_address: None,
+                    referral_commission: None,
+                })?,
+            })?,
+            funds: vec![],
+        }),
+    };
+
+    let convert_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::ConvertSwapped { recipient: sender.clone(), prev_balance })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_attribute("action", "zap_convert")
+        .add_attribute("offer_asset", offer.info.to_string())
+        .add_attribute("offer_amount", offer.amount.to_string())
+        .add_attribute("minimum_receive", minimum_receive.to_string())
+        .add_attribute("to", sender)
+        .add_messages(vec![swap_msg, convert_msg])
+    )
+}
+
+pub fn execute_convert_swapped(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    prev_balance: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_eq!(info.sender, env.contract.address, ContractError::Unauthorized {});
+
+    let config = CONFIG.load(deps.storage)?;
+    let balance = query_token_balance(deps.querier, config.protocol.adapter().token().clone(), env.contract.address.clone())?;
+
+    convert(deps, env, config, recipient, balance.checked_sub(prev_balance).map_err(StdError::from)?)
+}
+
+pub fn execute_redeem(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: String,
+    amount: Uint128
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Withdraw)?;
+    let config = CONFIG.load(deps.storage)?;
+    let adapter = config.protocol.adapter();
+
+    ensure_eq!(Some(info.sender.clone()), config.btoken, ContractError::InvalidToken {});
+    ensure_eq!((amount.gt(&Uint128::zero())), true, ContractError::InvalidZeroAmount {});
+
+    let sender = deps.api.addr_validate(&sender)?;
+
+    let delegations = take_delegations(deps.storage, amount)?;
+    add_to_backing(deps.storage, |backing| &mut backing.redeemed, amount)?;
+
+    let mut messages = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: info.sender.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
+            funds: vec![],
+        }),
+    ];
+    messages.extend(adapter.unbond_msgs(&delegations)?);
+
+    let mut attrs = vec![
+        ("action", "burn_token".to_string()),
+        ("token", info.sender.to_string()),
+        ("from", sender.to_string()),
+        ("amount", amount.to_string()),
+    ];
+
+    // the redeemed tokens can be claimed once the staking contract releases them
+    for delegation in delegations {
+        CLAIMS.create_claim(
+            deps.storage,
+            &sender,
+            delegation.amount,
+            Duration::Time(delegation.unbonding_period).after(&env.block),
+        )?;
+
+        attrs.push(("action", "unbond_token".to_string()));
+        attrs.push(("token", adapter.token().to_string()));
+        attrs.push(("from", adapter.staking_contract().to_string()));
+        attrs.push(("unbonding_period", delegation.unbonding_period.to_string()));
+        attrs.push(("amount", delegation.amount.to_string()));
+    }
+
+    Ok(Response::new()
+        .add_attributes(attrs)
+        .add_messages(messages)
+    )
+}
+
+pub fn execute_claim_redeemed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Claim)?;
+    let config = CONFIG.load(deps.storage)?;
+    let adapter = config.protocol.adapter();
+
+    let amount = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
+    ensure_eq!((amount.gt(&Uint128::zero())), true, ContractError::NothingToClaim {});
+
+    let mut messages = vec![];
+
+    // released tokens stay in the staking contract until someone claims them
+    let balance = query_token_balance(deps.querier, adapter.token().clone(), env.contract.address)?;
+    if balance.lt(&amount) {
+        messages.extend(adapter.claim_msgs()?);
+    }
+
+    messages.push(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: adapter.token().to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient: info.sender.to_string(), amount })?,
+            funds: vec![],
+        })
+    );
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_redeemed")
+        .add_attribute("to", info.sender.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_messages(messages)
+    )
+}
+
+pub fn execute_withdraw_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let adapter = config.protocol.adapter();
+
+    let btoken = config.btoken.clone().unwrap().to_string();
+    let btoken_vault = config.btoken_vault.clone().unwrap().to_string();
+
+    let withdrawable_amount = adapter.pending_rewards(deps.querier, &env.contract.address)?;
+
+    ensure_eq!(withdrawable_amount.gt(&Uint128::zero()), true, ContractError::InvalidZeroAmount {});
+
+    // the vault harvests before every stake change, it is neither paid nor held to the minimum
+    let bounty = if info.sender.as_str() == btoken_vault {
+        Uint128::zero()
+    } else {
+        ensure_eq!((withdrawable_amount >= config.min_harvest), true, ContractError::HarvestTooSmall(config.min_harvest));
+        // the keeper is paid out of the harvest, the vault gets the rest
+        keeper_bounty(&config, withdrawable_amount)
+    };
+    let distributed_amount = withdrawable_amount - bounty;
+
+    let mut attrs = vec![
+        ("action", "withdraw_rewards".to_string()),
+        ("amount", withdrawable_amount.to_string()),
+        ("action", "mint_token".to_string()),
+        ("token", btoken.clone()),
+        ("to", btoken_vault.clone()),
+        ("amount", distributed_amount.to_string()),
+    ];
+
+    let delegations = split_by_weight(&adapter.unbonding_tiers(), withdrawable_amount);
+    add_delegations(deps.storage, &delegations)?;
+    add_to_backing(deps.storage, |backing| &mut backing.compounded, withdrawable_amount)?;
+
+    let mut messages = adapter.compound_msgs(&delegations)?;
+
+    if !bounty.is_zero() {
+        attrs.push(("action", "keeper_bounty".to_string()));
+        attrs.push(("token", btoken.clone()));
+        attrs.push(("to", info.sender.to_string()));
+        attrs.push(("amount", bounty.to_string()));
+
+        messages.push(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: btoken.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: info.sender.to_string(), amount: bounty })?,
+                funds: vec![],
+            })
+        );
+    }
+
+    if !distributed_amount.is_zero() {
+        messages.push(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                //sending reward to user
+                contract_addr: btoken.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint { recipient: env.contract.address.to_string(), amount: distributed_amount })?,
+                funds: vec![],
+            })
+        );
+        messages.push(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: btoken,
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: btoken_vault,
+                    amount: distributed_amount,
+                    msg: to_binary(&VaultCw20HookMsg::DistributeRewards { address: None })?,
+                })?,
+                funds: vec![] 
+            })
+        );
+    }
+
+    Ok(Response::new()
+        .add_attributes(attrs)
+        .add_messages(messages)
+    )
+}
+
+fn keeper_bounty(config: &Config, rewards: Uint128) -> Uint128 {
+    rewards.multiply_ratio(config.keeper_bounty_bps, BPS_DENOMINATOR)
+}
+
+pub fn add_delegations(
+    storage: &mut dyn Storage,
+    delegations: &[Delegation],
+) -> Result<(), ContractError> {
+    for delegation in delegations {
+        DELEGATED.update(storage, delegation.unbonding_period, |delegated| -> StdResult<_> {
+            Ok(delegated.unwrap_or_default().checked_add(delegation.amount)?)
+        })?;
+    }
+    Ok(())
+}
+
+// Redemptions are served from the shortest unbonding periods first.
+pub fn take_delegations(
+    storage: &mut dyn Storage,
+    amount: Uint128,
+) -> Result<Vec<Delegation>, ContractError> {
+    let delegated = DELEGATED
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut remaining = amount;
+    let mut delegations = vec![];
+    for (unbonding_period, staked) in delegated {
+        if remaining.is_zero() {
+            break;
+        }
+        let taken = std::cmp::min(staked, remaining);
+        if taken.is_zero() {
+            continue;
+        }
+        remaining -= taken;
+        DELEGATED.save(storage, unbonding_period, &(staked - taken))?;
+        delegations.push(Delegation { unbonding_period, amount: taken });
+    }
+
+    ensure_eq!(remaining.is_zero(), true, ContractError::InsufficientDelegation {});
+
+    Ok(delegations)
+}
+
+pub fn add_to_backing(
+    storage: &mut dyn Storage,
+    total: fn(&mut BackingInfo) -> &mut Uint128,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let mut backing = BACKING.may_load(storage)?.unwrap_or_default();
+    let total = total(&mut backing);
+    *total = total.checked_add(amount)?;
+    BACKING.save(storage, &backing)?;
+    Ok(())
+}
+
+// Returns the bToken supply and the amount the staking module holds for the converter.
+fn query_backed_amounts(deps: Deps, env: &Env, config: &Config) -> StdResult<(Uint128, Uint128)> {
+    let adapter = config.protocol.adapter();
+
+    // periods dropped from the config may still hold delegations
+    let mut periods: Vec<u64> = adapter.unbonding_tiers().iter().map(|tier| tier.unbonding_period).collect();
+    for unbonding_period in DELEGATED.keys(deps.storage, None, None, Order::Ascending) {
+        let unbonding_period = unbonding_period?;
+        if !periods.contains(&unbonding_period) {
+            periods.push(unbonding_period);
+        }
+    }
+
+    let total_supply = query_token_supply(deps.querier, config.btoken.clone().unwrap())?;
+    let delegated = adapter.delegated(deps.querier, &env.contract.address, &periods)?;
+
+    Ok((total_supply, delegated))
+}
+
+pub fn execute_schedule_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: AdminAction,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let timelock_config = TIMELOCK_CONFIG.load(deps.storage)?;
+
+    ensure_eq!(info.sender, config.admin, ContractError::Unauthorized {});
+
+    // allowlisted messages like airdrop claims do not need to wait
+    if let AdminAction::ExecuteCosmosMsgs { msgs } = &action {
+        ensure_no_btoken_msgs(&config, msgs)?;
+        if !msgs.is_empty() && msgs.iter().all(|msg| is_allowlisted(&timelock_config.allowlist, msg)) {
+            return Ok(Response::new()
+                .add_attribute("action", "execute_allowlisted_msgs")
+                .add_messages(msgs.clone())
+            );
+        }
+    }
+
+    let id = NEXT_ACTION_ID.load(deps.storage)?;
+    NEXT_ACTION_ID.save(deps.storage, &(id + 1))?;
+
+    let scheduled_action = ScheduledAction {
+        id,
+        action,
+        ready_at: env.block.time.seconds() + timelock_config.delay,
+    };
+    SCHEDULED_ACTIONS.save(deps.storage, id, &scheduled_action)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "schedule_action")
+        .add_attribute("id", id.to_string())
+        .add_attribute("ready_at", scheduled_action.ready_at.to_string())
+    )
+}
+
+// Raw messages to the bToken would get around the backing check of `AdminAction::Mint`.
+fn ensure_no_btoken_msgs(config: &Config, msgs: &[CosmosMsg]) -> Result<(), ContractError> {
+    let targets_btoken = |msg: &CosmosMsg| match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. })
+        | CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. })
+        | CosmosMsg::Wasm(WasmMsg::UpdateAdmin { contract_addr, .. })
+        | CosmosMsg::Wasm(WasmMsg::ClearAdmin { contract_addr }) => {
+            config.btoken.as_ref().map(|btoken| btoken.as_str()) == Some(contract_addr.as_str())
+        },
+        _ => false,
+    };
+    ensure_eq!(msgs.iter().any(targets_btoken), false, ContractError::BTokenMsgNotAllowed {});
+    Ok(())
+}
+
+fn is_allowlisted(allowlist: &[AllowedMsg], msg: &CosmosMsg) -> bool {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) if funds.is_empty() => {
+            let msg_name = match from_slice::<BTreeMap<String, IgnoredAny>>(msg) {
+                Ok(fields) if fields.len() == 1 => fields.into_keys().next().unwrap(),
+                _ => return false,
+            };
+            allowlist.iter().any(|allowed| allowed.contract == *contract_addr && allowed.msg == msg_name)
+        },
+        _ => false,
+    }
+}
+
+pub fn execute_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    ensure_eq!(info.sender, config.admin, ContractError::Unauthorized {});
+
+    let scheduled_action = SCHEDULED_ACTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ActionNotFound(id))?;
+    if env.block.time.seconds() < scheduled_action.ready_at {
+        return Err(ContractError::ActionNotReady(scheduled_action.ready_at));
+    }
+
+    SCHEDULED_ACTIONS.remove(deps.storage, id);
+
+    let res = Response::new()
+        .add_attribute("action", "execute_action")
+        .add_attribute("id", id.to_string());
+
+    match scheduled_action.action {
+        AdminAction::ExecuteCosmosMsgs { msgs } => {
+            ensure_no_btoken_msgs(&config, &msgs)?;
+            Ok(res.add_messages(msgs))
+        },
+        AdminAction::Mint { recipient, amount } => {
+            let mint_res = execute_mint(deps, env, config, recipient, amount)?;
+            Ok(res.add_attributes(mint_res.attributes).add_submessages(mint_res.messages))
+        },
+        AdminAction::UpdateTimelockConfig { delay, guardian, allowlist } => {
+            let update_res = execute_update_timelock_config(deps, delay, guardian, allowlist)?;
+            Ok(res.add_attributes(update_res.attributes))
+        },
+    }
+}
+
+pub fn execute_cancel_action(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let timelock_config = TIMELOCK_CONFIG.load(deps.storage)?;
+
+    ensure_eq!(
+        info.sender == config.admin || Some(info.sender.clone()) == timelock_config.guardian,
+        true,
+        ContractError::Unauthorized {}
+    );
+    ensure_eq!(SCHEDULED_ACTIONS.has(deps.storage, id), true, ContractError::ActionNotFound(id));
+
+    SCHEDULED_ACTIONS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_action")
+        .add_attribute("id", id.to_string())
+        .add_attribute("by", info.sender.to_string())
+    )
+}
+
+fn execute_update_timelock_config(
+    deps: DepsMut,
+    delay: Option<u64>,
+    guardian: Option<String>,
+    allowlist: Option<Vec<AllowedMsg>>,
+) -> Result<Response, ContractError> {
+    let mut timelock_config = TIMELOCK_CONFIG.load(deps.storage)?;
+
+    let mut changed = false;
+
+    let mut response = Response::new().add_attribute("action", "update_timelock_config");
+
+    if let Some(delay) = delay {
+        changed = true;
+        timelock_config.delay = delay;
+        response = response.add_attribute("delay", delay.to_string());
+    }
+
+    if let Some(guardian) = guardian {
+        changed = true;
+        timelock_config.guardian = Some(deps.api.addr_validate(&guardian)?);
+        response = response.add_attribute("guardian", guardian);
+    }
+
+    if let Some(allowlist) = allowlist {
+        changed = true;
+        for allowed in allowlist.iter() {
+            deps.api.addr_validate(allowed.contract.as_str())?;
+            response = response.add_attribute("allowed_msg", format!("{}:{}", allowed.contract, allowed.msg));
+        }
+        timelock_config.allowlist = allowlist;
+    }
+
+    ensure_eq!(changed, true, ContractError::InvalidParams {});
+
+    TIMELOCK_CONFIG.save(deps.storage, &timelock_config)?;
+
+    Ok(response)
+}
+
+fn execute_mint(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    recipient: String,
+    amount: Uint128
+) -> Result<Response, ContractError> {
+    // bTokens minted without a deposit must already be backed by the delegated tokens
+    let (total_supply, delegated) = query_backed_amounts(deps.as_ref(), &env, &config)?;
+    if total_supply.checked_add(amount)? > delegated {
+        return Err(ContractError::UnbackedMint { amount, total_supply, delegated });
+    }
+
+    add_to_backing(deps.storage, |backing| &mut backing.minted, amount)?;
+
+    let attrs = vec![
+        ("action", "mint_token".to_string()),
+        ("token", config.btoken.clone().unwrap().to_string()),
+        ("to", recipient.clone()),
+        ("amount", amount.to_string()),
+    ];
+
+    let messages = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            //sending reward to user
+            contract_addr: config.btoken.unwrap().to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint { recipient, amount })?,
+            funds: vec![],
+        }),
+    ];
+
+    Ok(Response::new()
+        .add_attributes(attrs)
+        .add_messages(messages)
+    )
+}
+
+pub fn execute_vote(
+    deps: DepsMut, 
+    _env: Env, 
+    sender: Addr, 
+    vote: GaugeVote
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let gauge_cfg = GAUGE_CONFIG.load(deps.storage)?;
+    
+    ensure_eq!(gauge_cfg.synergistic_gauge_contract.is_some(), true, ContractError::InvalidAddr {});
+    ensure_eq!(gauge_cfg.synergistic_gauge_contract.unwrap(), sender.to_string(), ContractError::Unauthorized {});
+
+    let proposal_id = match &vote {
+        GaugeVote::PlaceVotes { gauge, .. } => *gauge,
+        GaugeVote::Vote { proposal_id, .. } => *proposal_id,
+    };
+    let message = config.protocol.adapter().vote_msg(&gauge_cfg.gauge_contract, vote)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "synergistic gauge vote")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_message(message)
+    )
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryConfig {} => to_binary(&query_config(deps)?),
+        QueryMsg::OwnershipProposal {} => to_binary(&OWNERSHIP_PROPOSAL.query_ownership_proposal(deps)?),
+        QueryMsg::IsPaused {} => to_binary(&PAUSABLE.query_is_paused(deps)?),
+        QueryMsg::QueryGaugeConfig {} => to_binary(&query_gauge_config(deps)?),
+        QueryMsg::Claims { address } => to_binary(&query_claims(deps, address)?),
+        QueryMsg::Delegations {} => to_binary(&query_delegations(deps)?),
+        QueryMsg::QueryTimelockConfig {} => to_binary(&query_timelock_config(deps)?),
+        QueryMsg::ExpectedBounty {} => to_binary(&query_expected_bounty(deps, env)?),
+        QueryMsg::BackingRatio {} => to_binary(&query_backing_ratio(deps, env)?),
+        QueryMsg::ScheduledActions { start_after, limit } => to_binary(&query_scheduled_actions(deps, start_after, limit)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    Ok(ConfigResponse {
+        config: CONFIG.load(deps.storage)?,
+    })
+}
+
+pub fn query_gauge_config(deps: Deps) -> StdResult<GaugeConfigResponse> {
+    Ok(GaugeConfigResponse {
+        gauge_config: GAUGE_CONFIG.load(deps.storage)?,
+    })
+}
+
+pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
+    CLAIMS.query_claims(deps, &deps.api.addr_validate(&address)?)
+}
+
+pub fn query_delegations(deps: Deps) -> StdResult<DelegationsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let tiers = config.protocol.adapter().unbonding_tiers();
+
+    let mut delegations: Vec<TierDelegation> = tiers
+        .iter()
+        .map(|tier| Ok(TierDelegation {
+            unbonding_period: tier.unbonding_period,
+            weight: tier.weight,
+            delegated: DELEGATED.may_load(deps.storage, tier.unbonding_period)?.unwrap_or_default(),
+        }))
+        .collect::<StdResult<_>>()?;
+
+    // periods dropped from the config may still hold delegations
+    for item in DELEGATED.range(deps.storage, None, None, Order::Ascending) {
+        let (unbonding_period, delegated) = item?;
+        if !delegated.is_zero() && !tiers.iter().any(|t| t.unbonding_period == unbonding_period) {
+            delegations.push(TierDelegation {
+                unbonding_period,
+                weight: Decimal::zero(),
+                delegated,
+            });
+        }
+    }
+
+    Ok(DelegationsResponse { delegations })
+}
+
+pub fn query_timelock_config(deps: Deps) -> StdResult<TimelockConfigResponse> {
+    Ok(TimelockConfigResponse {
+        timelock_config: TIMELOCK_CONFIG.load(deps.storage)?,
+    })
+}
+
+pub fn query_expected_bounty(deps: Deps, env: Env) -> StdResult<ExpectedBountyResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let pending_rewards = config.protocol.adapter().pending_rewards(deps.querier, &env.contract.address)?;
+    let harvestable = !pending_rewards.is_zero() && pending_rewards >= config.min_harvest;
+
+    Ok(ExpectedBountyResponse {
+        pending_rewards,
+        bounty: if harvestable { keeper_bounty(&config, pending_rewards) } else { Uint128::zero() },
+        min_harvest: config.min_harvest,
+        harvestable,
+    })
+}
+
+pub fn query_backing_ratio(deps: Deps, env: Env) -> StdResult<BackingRatioResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let backing = BACKING.may_load(deps.storage)?.unwrap_or_default();
+
+    let (total_supply, delegated) = query_backed_amounts(deps, &env, &config)?;
+    let ratio = if total_supply.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::checked_from_ratio(delegated, total_supply)
+            .map_err(|e| StdError::generic_err(e.to_string()))?
+    };
+
+    Ok(BackingRatioResponse {
+        total_supply,
+        delegated,
+        ratio,
+        deposited: backing.deposited,
+        compounded: backing.compounded,
+        redeemed: backing.redeemed,
+        minted: backing.minted,
+    })
+}
+
+pub fn query_scheduled_actions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ScheduledActionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let actions = SCHEDULED_ACTIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, action)| action))
+        .collect::<StdResult<_>>()?;
+
+    Ok(ScheduledActionsResponse { actions })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::FromV1 {
+            unbonding_periods,
+            timelock_delay,
+            guardian,
+            keeper_bounty_bps,
+            min_harvest,
+            swap_router,
+        } => migrate_from_v1(
+            deps,
+            env,
+            unbonding_periods,
+            timelock_delay,
+            guardian,
+            keeper_bounty_bps,
+            min_harvest,
+            swap_router,
+        ),
+        MigrateMsg::FromCompatible {} => {
+            let original_version = ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+            if original_version < first_merged_version() {
+                return Err(ContractError::UnsupportedMigration {
+                    contract: CONTRACT_NAME.to_string(),
+                    version: original_version.to_string(),
+                });
+            }
+
+            Ok(Response::new()
+                .add_attribute("action", "migrate")
+                .add_attribute("from_version", original_version.to_string()))
+        }
+    }
+}
+
+/// First version storing the merged layout, everything older is a v1 converter
+fn first_merged_version() -> Version {
+    "1.1.0".parse().unwrap()
+}
+
+/// Rewrites the config of a bWYND or bLOOP converter and seeds the state added since.
+#[allow(clippy::too_many_arguments)]
+fn migrate_from_v1(
+    deps: DepsMut,
+    env: Env,
+    unbonding_periods: Option<Vec<UnbondingTier>>,
+    timelock_delay: u64,
+    guardian: Option<String>,
+    keeper_bounty_bps: u64,
+    min_harvest: Uint128,
+    swap_router: Option<String>,
+) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    let unsupported = || ContractError::UnsupportedMigration {
+        contract: stored.contract.clone(),
+        version: stored.version.clone(),
+    };
+    let stored_version: Version = stored.version.parse().map_err(|_| unsupported())?;
+    if stored_version >= first_merged_version() {
+        return Err(unsupported());
+    }
+
+    // the only unbonding period the v1 converters staked with
+    let (mut config, gauge_config, staked_period) = match stored.contract.as_str() {
+        v1::WYND_CONTRACT_NAME => {
+            let old = v1::WYND_CONFIG.load(deps.storage)?;
+            let old_gauge = v1::WYND_GAUGE_CONFIG.load(deps.storage)?;
+            let protocol = UnderlyingProtocol::Wynd(WyndStaking {
+                wynd_token: old.wynd_token,
+                wynd_staking_module: old.wynd_staking_module,
+                unbonding_periods: vec![UnbondingTier {
+                    unbonding_period: old.unbonding_period,
+                    weight: Decimal::one(),
+                }],
+            });
+            let config = Config {
+                admin: old.admin,
+                cw20_code_id: old.cw20_code_id,
+                vault_code_id: old.vault_code_id,
+                protocol,
+                btoken: old.bwynd,
+                btoken_vault: old.bwynd_vault,
+                keeper_bounty_bps: 0,
+                min_harvest: Uint128::zero(),
+                swap_router: None,
+            };
+            let gauge_config = GaugeConfig {
+                gauge_contract: old_gauge.wynd_gauge_contract,
+                synergistic_gauge_contract: old_gauge.synergistic_wynd_gauge_contract,
+            };
+            (config, gauge_config, old.unbonding_period)
+        }
+        v1::LOOP_CONTRACT_NAME => {
+            let old = v1::LOOP_CONFIG.load(deps.storage)?;
+            let old_gauge = v1::LOOP_GAUGE_CONFIG.load(deps.storage)?;
+            let protocol = UnderlyingProtocol::Loop(LoopStaking {
+                loop_token: old.loop_token,
+                loop_protocol_staking: old.loop_protocol_staking,
+                duration: old.duration,
+            });
+            let config = Config {
+                admin: old.admin,
+                cw20_code_id: old.cw20_code_id,
+                vault_code_id: old.vault_code_id,
+                protocol,
+                btoken: old.bloop_token,
+                btoken_vault: old.bloop_vault,
+                keeper_bounty_bps: 0,
+                min_harvest: Uint128::zero(),
+                swap_router: None,
+            };
+            let gauge_config = GaugeConfig {
+                gauge_contract: old_gauge.loop_gauge_contract,
+                synergistic_gauge_contract: old_gauge.synergistic_loop_gauge_contract,
+            };
+            (config, gauge_config, old.duration)
+        }
+        _ => return Err(unsupported()),
+    };
+
+    if let Some(unbonding_periods) = unbonding_periods {
+        config.protocol.adapter_mut().set_unbonding_tiers(unbonding_periods)?;
+    }
+    config.keeper_bounty_bps = validate_keeper_bounty(keeper_bounty_bps)?;
+    config.min_harvest = min_harvest;
+    config.swap_router = swap_router.map(|router| deps.api.addr_validate(&router)).transpose()?;
+
+    // everything staked so far sits in the single v1 unbonding period
+    let staked = config.protocol.adapter().delegated(deps.querier, &env.contract.address, &[staked_period])?;
+    if !staked.is_zero() {
+        DELEGATED.save(deps.storage, staked_period, &staked)?;
+    }
+
+    // v1 kept no history of the supply, so all of it counts as deposited
+    let total_supply = match &config.btoken {
+        Some(btoken) => query_token_supply(deps.querier, btoken)?,
+        None => Uint128::zero(),
+    };
+    BACKING.save(deps.storage, &BackingInfo { deposited: total_supply, ..BackingInfo::default() })?;
+
+    let timelock_config = TimelockConfig {
+        delay: timelock_delay,
+        guardian: guardian.map(|guardian| deps.api.addr_validate(&guardian)).transpose()?,
+        allowlist: vec![],
+    };
+    TIMELOCK_CONFIG.save(deps.storage, &timelock_config)?;
+    PAUSABLE.set_guardian(deps.storage, timelock_config.guardian)?;
+    NEXT_ACTION_ID.save(deps.storage, &1u64)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    GAUGE_CONFIG.save(deps.storage, &gauge_config)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_contract", stored.contract)
+        .add_attribute("from_version", stored.version)
+        .add_attribute("delegated", staked))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.result.is_err() {
+        return Err(ContractError::ErrorReply {});
+    }
+    match msg.id {
+        INSTANTIATE_BTOKEN_ID => {
+            // parse the reply
+            let res = cw_utils::parse_reply_instantiate_data(msg).map_err(|_| {
+                StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
+            })?;
+            reply_btoken_instantiate(deps, env, res)
+        },
+        INSTANTIATE_VAULT_ID => {
+            let res = cw_utils::parse_reply_instantiate_data(msg).map_err(|_| {
+                StdError::parse_err("MsgInstantiateContractResponse", "failed to parse data")
+            })?;
+            reply_btoken_vault_instantiate(deps, env, res)
+        },
+        x => Err(ContractError::UnknownReply(x)),
+    }
+}
+
+pub fn reply_btoken_instantiate(deps: DepsMut, _env: Env, res: MsgInstantiateContractResponse) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let btoken = deps.api.addr_validate(&res.contract_address)?;
+
+    config.btoken = Some(btoken.clone());
+
+    CONFIG.save(deps.storage, &config)?;
+
+    let sub_msg: Vec<SubMsg> = vec![SubMsg {
+        id: INSTANTIATE_VAULT_ID,
+        msg: WasmMsg::Instantiate {
+            admin: Some(config.admin.to_string()),
+            code_id: config.vault_code_id,
+            msg: config.protocol.adapter().vault_instantiate_msg(
+                &config.admin,
+                &btoken,
+                Uint128::from(1000000u128),
+            )?,
+            funds: vec![],
+            label: "btoken Vault Contract".to_string(),
+        }
+        .into(),
+        gas_limit: None,
+        reply_on: ReplyOn::Success,
+    }];
+
+    Ok(Response::new()
+        .add_submessages(sub_msg)
+        .add_attribute("bToken", btoken.to_string())
+    )
+}
+
+pub fn reply_btoken_vault_instantiate(deps: DepsMut, _env: Env, res: MsgInstantiateContractResponse) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let btoken_vault_address = deps.api.addr_validate(&res.contract_address)?;
+
+    config.btoken_vault = Some(btoken_vault_address.clone());
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("btoken Vault Contract", btoken_vault_address.to_string())
+    )
+}
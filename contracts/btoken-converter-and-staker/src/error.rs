@@ -0,0 +1,114 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cw_controllers::{OwnershipError, PausableError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Pausable(#[from] PausableError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Cannot set to own account")]
+    CannotSetOwnAccount {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Allowance is expired")]
+    Expired {},
+
+    #[error("No allowance for this account")]
+    NoAllowance {},
+
+    #[error("Action is required")]
+    NoAction {},
+
+    #[error("Minting cannot exceed the cap")]
+    CannotExceedCap {},
+
+    #[error("Logo binary data exceeds 5KB limit")]
+    LogoTooBig {},
+
+    #[error("Invalid xml preamble for SVG")]
+    InvalidXmlPreamble {},
+
+    #[error("Invalid png header")]
+    InvalidPngHeader {},
+
+    #[error("Duplicate initial balance addresses")]
+    DuplicateInitialBalanceAddresses {},
+
+    #[error("Invalid token")]
+    InvalidToken {},
+
+    #[error("Invalid address")]
+    InvalidAddr {},
+    
+    #[error("Got reply with error, only handle success case")]
+    ErrorReply {},
+
+    #[error("Got reply with unknown ID: {0}")]
+    UnknownReply(u64),
+
+    #[error("Undefined")]
+    GenericErr {},
+
+    #[error("Unbonding period is not set")]
+    UnbondingPeriodErr {},
+
+    #[error("Invalid parameters")]
+    InvalidParams {},
+
+    #[error("No claims that can be released currently")]
+    NothingToClaim {},
+
+    #[error("Redeeming is not supported by the underlying protocol")]
+    RedeemNotSupported {},
+
+    #[error("Vote does not match the gauge of the underlying protocol")]
+    InvalidVote {},
+
+    #[error("Unbonding periods must be distinct with weights summing up to 1")]
+    InvalidUnbondingTiers {},
+
+    #[error("Not enough delegated tokens to redeem")]
+    InsufficientDelegation {},
+
+    #[error("Minting {amount} would leave the bToken supply of {total_supply} backed by {delegated} only")]
+    UnbackedMint { amount: Uint128, total_supply: Uint128, delegated: Uint128 },
+
+    #[error("Admin messages cannot target the bToken, use the Mint action instead")]
+    BTokenMsgNotAllowed {},
+
+    #[error("Keeper bounty can be at most {0} bps")]
+    KeeperBountyTooHigh(u64),
+
+    #[error("Pending rewards are below the minimum harvest of {0}")]
+    HarvestTooSmall(Uint128),
+
+    #[error("No swap router configured")]
+    NoSwapRouter {},
+
+    #[error("Swap route must start with the sent asset and end in the underlying token")]
+    InvalidSwapRoute {},
+
+    #[error("Scheduled action {0} not found")]
+    ActionNotFound(u64),
+
+    #[error("Scheduled action can be executed at {0}")]
+    ActionNotReady(u64),
+
+    #[error("Cannot migrate from {contract} {version}")]
+    UnsupportedMigration { contract: String, version: String },
+}
@@ -1,5 +1,10 @@
+pub mod adapter;
 pub mod contract;
 pub mod msg;
+pub mod queriers;
 pub mod state;
 pub mod error;
-pub mod queriers;
+mod v1;
+
+#[cfg(test)]
+mod testing;
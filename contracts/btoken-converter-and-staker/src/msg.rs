@@ -0,0 +1,273 @@
+use cw20::{Cw20ReceiveMsg, Cw20Coin, MinterResponse};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use syneswap::staking::InstantiateMarketingInfo;
+use crate::adapter::UnbondingTier;
+use crate::state::{Config, Vote, MultipleChoiceVote, GaugeConfig, AdminAction, ScheduledAction, TimelockConfig};
+use cosmwasm_std::{Decimal, Uint128, Addr};
+use synedex::multi_hop::SwapOperation;
+use cw_controllers::PausableOperation;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub admin: String,
+    pub cw20_code_id: u64,
+    pub vault_code_id: u64,
+    /// The protocol the underlying token is staked in
+    pub protocol: ProtocolMsg,
+    pub gauge_contract: String,
+    pub synergistic_gauge_contract: Option<String>,
+    /// Seconds a scheduled admin action waits before it can be executed
+    pub timelock_delay: u64,
+    pub guardian: Option<String>,
+    /// Share of the harvested rewards paid to the caller of `WithdrawRewards`, in bps
+    pub keeper_bounty_bps: u64,
+    pub min_harvest: Uint128,
+    /// synedex multi-hop router used by `ZapConvert`
+    pub swap_router: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolMsg {
+    Wynd {
+        wynd_token: String,
+        wynd_staking_module: String,
+        /// WYND staking tiers new delegations are spread across
+        unbonding_periods: Vec<UnbondingTier>,
+    },
+    Loop {
+        loop_token: String,
+        loop_protocol_staking: String,
+        duration: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Queues an admin action. Allowlisted messages are executed right away, messages to the
+    /// bToken are refused.
+    ScheduleAction {
+        action: AdminAction,
+    },
+    /// Executes a queued admin action whose delay has passed
+    ExecuteAction {
+        id: u64,
+    },
+    /// Drops a queued admin action, callable by the admin or the guardian
+    CancelAction {
+        id: u64,
+    },
+    Receive(Cw20ReceiveMsg),
+    /// Proposes a new admin, who has `expires_in` seconds to claim the ownership
+    ProposeNewOwner {
+        owner: String,
+        expires_in: u64,
+    },
+    DropOwnershipProposal {},
+    /// Makes the proposed admin the new admin
+    ClaimOwnership {},
+    UpdateConfig {
+        /// Unbonding periods (WYND) or staking duration (LOOP) new stakes are spread across
+        unbonding_periods: Option<Vec<UnbondingTier>>,
+        keeper_bounty_bps: Option<u64>,
+        min_harvest: Option<Uint128>,
+        swap_router: Option<String>,
+    },
+    UpdateGaugeConfig {
+        gauge_contract: Option<String>,
+        synergistic_gauge_contract: Option<String>
+    },
+    // from bToken Vault
+    WithdrawRewards {},
+    /// Claims the underlying tokens of all redemptions whose unbonding period has passed
+    ClaimRedeemed {},
+    /// Swaps the sent native tokens into the underlying token and converts the proceeds
+    ZapConvert {
+        /// Route ending in the underlying token
+        operations: Vec<SwapOperation>,
+        minimum_receive: Uint128,
+        max_spread: Option<Decimal>,
+    },
+    /// Internal use, converts the underlying tokens received by a zap
+    ConvertSwapped {
+        recipient: String,
+        prev_balance: Uint128,
+    },
+    /// Vote of the synergistic WYND gauge
+    PlaceVotes {
+        gauge: u64,
+        votes: Option<Vec<Vote>>,
+    },
+    /// Vote of the synergistic LOOP gauge
+    Vote {
+        /// The ID of the proposal to vote on.
+        proposal_id: u64,
+        /// The senders position on the proposal.
+        vote: Vec<MultipleChoiceVote>,
+    },
+    /// Halts operations, callable by the admin or the guardian
+    Pause {
+        operations: Vec<PausableOperation>,
+    },
+    /// Resumes operations, callable by the admin
+    Unpause {
+        operations: Vec<PausableOperation>,
+    },
+    UpdatePauseGuardian {
+        guardian: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    QueryConfig {},
+    QueryGaugeConfig {},
+    IsPaused {},
+    OwnershipProposal {},
+    /// Shows the redeemed tokens waiting for the unbonding period of this address
+    Claims { address: String },
+    /// Shows the weight and the delegated amount of every unbonding period
+    Delegations {},
+    QueryTimelockConfig {},
+    /// Bounty the next `WithdrawRewards` would pay its caller
+    ExpectedBounty {},
+    /// Compares the bToken supply with the amount delegated in the staking module
+    BackingRatio {},
+    /// Lists the queued admin actions
+    ScheduledActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigResponse {
+    pub config: Config,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GaugeConfigResponse {
+    pub gauge_config: GaugeConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TierDelegation {
+    pub unbonding_period: u64,
+    /// Share of new stakes, zero for periods removed from the config
+    pub weight: Decimal,
+    pub delegated: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DelegationsResponse {
+    pub delegations: Vec<TierDelegation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TimelockConfigResponse {
+    pub timelock_config: TimelockConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ScheduledActionsResponse {
+    pub actions: Vec<ScheduledAction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct BackingRatioResponse {
+    pub total_supply: Uint128,
+    /// Underlying tokens the staking module reports as delegated by the converter
+    pub delegated: Uint128,
+    /// `delegated / total_supply`, one while nothing is minted
+    pub ratio: Decimal,
+    pub deposited: Uint128,
+    pub compounded: Uint128,
+    pub redeemed: Uint128,
+    pub minted: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ExpectedBountyResponse {
+    pub pending_rewards: Uint128,
+    pub bounty: Uint128,
+    pub min_harvest: Uint128,
+    /// Whether the pending rewards reach `min_harvest`
+    pub harvestable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMsg {
+    /// Migrates a bWYND or bLOOP converter (v1.0) to the merged layout.
+    /// The fields were added after v1.0 and must be set.
+    FromV1 {
+        /// WYND staking tiers replacing the single unbonding period of the bWYND converter,
+        /// which stays the only tier if not set
+        unbonding_periods: Option<Vec<UnbondingTier>>,
+        timelock_delay: u64,
+        guardian: Option<String>,
+        keeper_bounty_bps: u64,
+        min_harvest: Uint128,
+        swap_router: Option<String>,
+    },
+    /// Migrates a converter already using the current layout
+    FromCompatible {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    Convert {},
+    /// Burns the sent bTokens and unbonds the same amount of underlying tokens
+    Redeem {},
+    /// Swaps the sent cw20 into the underlying token and converts the proceeds
+    ZapConvert {
+        /// Route ending in the underlying token
+        operations: Vec<SwapOperation>,
+        minimum_receive: Uint128,
+        max_spread: Option<Decimal>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveDelegationMsg {
+    Delegate {
+        /// Unbonding period in seconds
+        unbonding_period: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[cfg_attr(test, derive(Default))]
+pub struct Cw20InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<MinterResponse>,
+    pub marketing: Option<InstantiateMarketingInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultCw20HookMsg {
+    DistributeRewards { address: Option<Addr> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VaultExecuteMsg {
+    DistributeRewards { address: Addr },
+}
@@ -0,0 +1,122 @@
+use cosmwasm_std::{
+    QuerierWrapper, StdResult, Uint128
+};
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct WithdrawableRewardsResponse {
+    /// Amount of rewards assigned for withdrawal from the given address.
+    pub rewards: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserRewardResponse {
+    pub user_reward: Uint128,
+    pub calculated_days_of_reward: u64,
+    pub pending_reward: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct StakedResponse {
+    pub stake: Uint128,
+    pub total_locked: Uint128,
+    pub unbonding_period: u64,
+    pub cw20_contract: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Return how many rewards are assigned for withdrawal from the given address. Returns
+    /// `RewardsResponse`.
+    WithdrawableRewards {
+        owner: String,
+    },
+    /// Return the LOOP rewards of the given wallet for a staking duration. Returns
+    /// `UserRewardResponse`.
+    QueryUserReward {
+        wallet: String,
+        duration: u64,
+    },
+    /// Return the WYND staked by the given address for an unbonding period. Returns
+    /// `StakedResponse`.
+    Staked {
+        address: String,
+        unbonding_period: u64,
+    },
+    /// Return the LOOP staked by the given wallet for a staking duration. Returns `Uint128`.
+    QueryStakedByUser {
+        wallet: String,
+        duration: u64,
+    },
+}
+
+pub fn query_wynd_dao_core_rewards (
+    querier: QuerierWrapper,
+    contract_addr: impl Into<String>,
+    account: impl Into<String>,
+ ) -> StdResult<WithdrawableRewardsResponse> {
+    let query_response: WithdrawableRewardsResponse =
+    querier.query_wasm_smart(contract_addr, &QueryMsg::WithdrawableRewards { owner: account.into() })?;
+    Ok(query_response)
+}
+
+pub fn query_loop_protocol_staking_rewards (
+    querier: QuerierWrapper,
+    contract_addr: impl Into<String>,
+    account: impl Into<String>,
+    duration: u64,
+ ) -> StdResult<UserRewardResponse> {
+    let query_response: UserRewardResponse =
+    querier.query_wasm_smart(contract_addr, &QueryMsg::QueryUserReward { wallet: account.into(), duration }).unwrap_or(UserRewardResponse {
+        user_reward: Uint128::zero(),
+        calculated_days_of_reward: 0,
+        pending_reward: Uint128::zero()
+    });
+    Ok(query_response)
+}
+
+pub fn query_wynd_dao_core_staked (
+    querier: QuerierWrapper,
+    contract_addr: impl Into<String>,
+    account: impl Into<String>,
+    unbonding_period: u64,
+ ) -> StdResult<Uint128> {
+    let query_response: StakedResponse =
+    querier.query_wasm_smart(contract_addr, &QueryMsg::Staked { address: account.into(), unbonding_period })?;
+    Ok(query_response.stake)
+}
+
+pub fn query_loop_protocol_staked (
+    querier: QuerierWrapper,
+    contract_addr: impl Into<String>,
+    account: impl Into<String>,
+    duration: u64,
+ ) -> StdResult<Uint128> {
+    querier.query_wasm_smart(contract_addr, &QueryMsg::QueryStakedByUser { wallet: account.into(), duration })
+}
+
+pub fn query_token_supply(
+    querier: QuerierWrapper,
+    contract_addr: impl Into<String>,
+) -> StdResult<Uint128> {
+    let query_response: TokenInfoResponse =
+    querier.query_wasm_smart(contract_addr, &Cw20QueryMsg::TokenInfo {})?;
+    Ok(query_response.total_supply)
+}
+
+pub fn query_token_balance(
+    querier: QuerierWrapper,
+    contract_addr: impl Into<String>,
+    account: impl Into<String>,
+) -> StdResult<Uint128> {
+    let query_response: BalanceResponse =
+    querier.query_wasm_smart(contract_addr, &Cw20QueryMsg::Balance { address: account.into() })?;
+    Ok(query_response.balance)
+}
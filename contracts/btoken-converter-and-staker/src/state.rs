@@ -0,0 +1,119 @@
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Uint128};
+use cw_controllers::{Claims, Ownership, Pausable};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::adapter::UnderlyingProtocol;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct Config {
+    pub admin: Addr,
+    pub cw20_code_id: u64,
+    pub vault_code_id: u64, 
+    /// The protocol the underlying token is staked in
+    pub protocol: UnderlyingProtocol,
+    pub btoken: Option<Addr>,
+    pub btoken_vault: Option<Addr>,
+    /// Share of the harvested rewards minted to the caller of `WithdrawRewards`, in bps
+    pub keeper_bounty_bps: u64,
+    /// Smallest reward amount `WithdrawRewards` harvests
+    pub min_harvest: Uint128,
+    /// synedex multi-hop router used by `ZapConvert`
+    pub swap_router: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct GaugeConfig {
+    pub gauge_contract: String,
+    pub synergistic_gauge_contract: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct Vote {
+    /// Option voted for.
+    pub option: String,
+    /// The weight of the power given to this vote
+    pub weight: Decimal,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct MultipleChoiceVote {
+    // A vote indicates which option the user has selected.
+    pub pool: String,
+    pub percentage: u32,
+}
+
+/// Running totals of everything that changed the bToken supply.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, JsonSchema)]
+pub struct BackingInfo {
+    /// Underlying tokens converted by users
+    pub deposited: Uint128,
+    /// Protocol rewards restaked and minted to the vault
+    pub compounded: Uint128,
+    /// bTokens burned for redemption
+    pub redeemed: Uint128,
+    /// bTokens minted by the admin
+    pub minted: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct TimelockConfig {
+    /// Seconds a scheduled admin action waits before it can be executed
+    pub delay: u64,
+    /// Address allowed to cancel scheduled actions besides the admin
+    pub guardian: Option<Addr>,
+    /// Messages that are executed without waiting for the delay
+    pub allowlist: Vec<AllowedMsg>,
+}
+
+/// A wasm execute message without funds, identified by the contract and the message name.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct AllowedMsg {
+    pub contract: Addr,
+    /// Top level key of the execute message, e.g. `claim`
+    pub msg: String,
+}
+
+/// Actions of the admin which have to go through the timelock.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    ExecuteCosmosMsgs {
+        msgs: Vec<CosmosMsg>,
+    },
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    UpdateTimelockConfig {
+        delay: Option<u64>,
+        guardian: Option<String>,
+        allowlist: Option<Vec<AllowedMsg>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduledAction {
+    pub id: u64,
+    pub action: AdminAction,
+    /// Time in seconds from which the action can be executed
+    pub ready_at: u64,
+}
+
+// put the length bytes at the first for compatibility with legacy singleton store
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const GAUGE_CONFIG: Item<GaugeConfig> = Item::new("gauge_config");
+
+// bToken redemptions waiting for the unbonding period to pass
+pub const CLAIMS: Claims = Claims::new("claims");
+
+// underlying tokens staked by the converter per unbonding period
+pub const DELEGATED: Map<u64, Uint128> = Map::new("delegated");
+
+pub const TIMELOCK_CONFIG: Item<TimelockConfig> = Item::new("timelock_config");
+pub const SCHEDULED_ACTIONS: Map<u64, ScheduledAction> = Map::new("scheduled_actions");
+pub const NEXT_ACTION_ID: Item<u64> = Item::new("next_action_id");
+pub const BACKING: Item<BackingInfo> = Item::new("backing");
+pub const PAUSABLE: Pausable = Pausable::new("pausable");
+pub const OWNERSHIP_PROPOSAL: Ownership = Ownership::new("ownership_proposal");
@@ -0,0 +1,971 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    attr, coins, from_binary, to_binary, Addr, Binary, Coin, ContractResult, CosmosMsg, Decimal, OwnedDeps,
+    Response, StdResult, SystemResult, SubMsg, Uint128, WasmMsg, WasmQuery,
+};
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse};
+
+use synedao::wynd_dao_core::ExecuteMsg as WyndDaoCoreExecuteMsg;
+use synedex::asset::AssetInfo;
+use synedex::multi_hop::{ExecuteMsg as MultiHopExecuteMsg, SwapOperation};
+
+use crate::adapter::{
+    split_by_weight, Delegation, LoopStaking, UnbondingTier, UnderlyingProtocol, UnderlyingStaking,
+    WyndStaking,
+};
+use crate::contract::{execute, migrate, take_delegations};
+use crate::error::ContractError;
+use crate::msg::{Cw20HookMsg, ExecuteMsg, MigrateMsg, VaultCw20HookMsg};
+use crate::queriers::{QueryMsg as ProtocolQueryMsg, StakedResponse, WithdrawableRewardsResponse};
+use crate::state::{
+    AdminAction, AllowedMsg, BackingInfo, Config, GaugeConfig, ScheduledAction, TimelockConfig,
+    BACKING, CONFIG, DELEGATED, GAUGE_CONFIG, NEXT_ACTION_ID, PAUSABLE, SCHEDULED_ACTIONS,
+    TIMELOCK_CONFIG,
+};
+use crate::v1;
+
+const STAKED: u128 = 1_000_000;
+const SUPPLY: u128 = 990_000;
+/// Below the `min_harvest` the tests migrate with
+const REWARDS: u128 = 50;
+
+/// Answers the staked amount, pending rewards and bToken supply queries.
+fn mock_deps_with_stake() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    mock_deps_with_balance(0)
+}
+
+/// Like `mock_deps_with_stake`, with `balance` underlying tokens held by the converter.
+fn mock_deps_with_balance(balance: u128) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart { contract_addr, msg } = query else {
+            panic!("unexpected query {:?}", query);
+        };
+        let response = match contract_addr.as_str() {
+            "btoken" => match from_binary(msg).unwrap() {
+                Cw20QueryMsg::TokenInfo {} => to_binary(&TokenInfoResponse {
+                    name: "bToken".to_string(),
+                    symbol: "bTOKEN".to_string(),
+                    decimals: 6,
+                    total_supply: Uint128::new(SUPPLY),
+                }),
+                other => panic!("unexpected cw20 query {:?}", other),
+            },
+            "wynd" | "loop" => match from_binary(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => to_binary(&BalanceResponse {
+                    balance: Uint128::new(balance),
+                }),
+                other => panic!("unexpected cw20 query {:?}", other),
+            },
+            "staking" => match from_binary(msg).unwrap() {
+                ProtocolQueryMsg::Staked { unbonding_period, .. } => to_binary(&StakedResponse {
+                    stake: Uint128::new(STAKED),
+                    total_locked: Uint128::zero(),
+                    unbonding_period,
+                    cw20_contract: "wynd".to_string(),
+                }),
+                ProtocolQueryMsg::QueryStakedByUser { .. } => to_binary(&Uint128::new(STAKED)),
+                ProtocolQueryMsg::WithdrawableRewards { .. } => {
+                    to_binary(&WithdrawableRewardsResponse {
+                        rewards: Uint128::new(REWARDS),
+                    })
+                }
+                other => panic!("unexpected staking query {:?}", other),
+            },
+            other => panic!("unexpected contract {}", other),
+        };
+        SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+    });
+    deps
+}
+
+fn from_v1_msg(unbonding_periods: Option<Vec<UnbondingTier>>) -> MigrateMsg {
+    MigrateMsg::FromV1 {
+        unbonding_periods,
+        timelock_delay: 86400,
+        guardian: Some("guardian".to_string()),
+        keeper_bounty_bps: 50,
+        min_harvest: Uint128::new(100),
+        swap_router: Some("router".to_string()),
+    }
+}
+
+fn seed_wynd_v1(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, version: &str) {
+    set_contract_version(deps.as_mut().storage, v1::WYND_CONTRACT_NAME, version).unwrap();
+    v1::WYND_CONFIG
+        .save(
+            deps.as_mut().storage,
+            &v1::WyndConfig {
+                admin: Addr::unchecked("admin"),
+                cw20_code_id: 1,
+                vault_code_id: 2,
+                wynd_token: Addr::unchecked("wynd"),
+                wynd_staking_module: Addr::unchecked("staking"),
+                bwynd: Some(Addr::unchecked("btoken")),
+                bwynd_vault: Some(Addr::unchecked("vault")),
+                unbonding_period: 86400 * 365,
+            },
+        )
+        .unwrap();
+    v1::WYND_GAUGE_CONFIG
+        .save(
+            deps.as_mut().storage,
+            &v1::WyndGaugeConfig {
+                wynd_gauge_contract: "wynd_gauge".to_string(),
+                synergistic_wynd_gauge_contract: Some("synergistic_gauge".to_string()),
+            },
+        )
+        .unwrap();
+}
+
+fn seed_loop_v1(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    set_contract_version(deps.as_mut().storage, v1::LOOP_CONTRACT_NAME, "1.0.0").unwrap();
+    v1::LOOP_CONFIG
+        .save(
+            deps.as_mut().storage,
+            &v1::LoopConfig {
+                admin: Addr::unchecked("admin"),
+                cw20_code_id: 1,
+                vault_code_id: 2,
+                loop_token: Addr::unchecked("loop"),
+                loop_protocol_staking: Addr::unchecked("staking"),
+                duration: 12,
+                bloop_token: Some(Addr::unchecked("btoken")),
+                bloop_vault: Some(Addr::unchecked("vault")),
+            },
+        )
+        .unwrap();
+    v1::LOOP_GAUGE_CONFIG
+        .save(
+            deps.as_mut().storage,
+            &v1::LoopGaugeConfig {
+                loop_gauge_contract: "loop_gauge".to_string(),
+                synergistic_loop_gauge_contract: None,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn migrate_bwynd_converter_from_v1() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    let config = CONFIG.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            admin: Addr::unchecked("admin"),
+            cw20_code_id: 1,
+            vault_code_id: 2,
+            protocol: UnderlyingProtocol::Wynd(WyndStaking {
+                wynd_token: Addr::unchecked("wynd"),
+                wynd_staking_module: Addr::unchecked("staking"),
+                unbonding_periods: vec![UnbondingTier {
+                    unbonding_period: 86400 * 365,
+                    weight: Decimal::one(),
+                }],
+            }),
+            btoken: Some(Addr::unchecked("btoken")),
+            btoken_vault: Some(Addr::unchecked("vault")),
+            keeper_bounty_bps: 50,
+            min_harvest: Uint128::new(100),
+            swap_router: Some(Addr::unchecked("router")),
+        }
+    );
+    assert_eq!(
+        GAUGE_CONFIG.load(deps.as_ref().storage).unwrap(),
+        GaugeConfig {
+            gauge_contract: "wynd_gauge".to_string(),
+            synergistic_gauge_contract: Some("synergistic_gauge".to_string()),
+        }
+    );
+
+    // the existing stake is booked under the v1 unbonding period
+    assert_eq!(
+        DELEGATED.load(deps.as_ref().storage, 86400 * 365).unwrap(),
+        Uint128::new(STAKED)
+    );
+    assert_eq!(
+        BACKING.load(deps.as_ref().storage).unwrap(),
+        BackingInfo {
+            deposited: Uint128::new(SUPPLY),
+            ..BackingInfo::default()
+        }
+    );
+
+    assert_eq!(
+        TIMELOCK_CONFIG.load(deps.as_ref().storage).unwrap(),
+        TimelockConfig {
+            delay: 86400,
+            guardian: Some(Addr::unchecked("guardian")),
+            allowlist: vec![],
+        }
+    );
+    assert_eq!(NEXT_ACTION_ID.load(deps.as_ref().storage).unwrap(), 1);
+    assert_eq!(
+        PAUSABLE.guardian(deps.as_ref()).unwrap(),
+        Some(Addr::unchecked("guardian"))
+    );
+
+    let version = get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.contract, env!("CARGO_PKG_NAME"));
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn migrate_bwynd_converter_with_new_tiers() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+
+    let tiers = vec![
+        UnbondingTier {
+            unbonding_period: 86400 * 365,
+            weight: Decimal::percent(60),
+        },
+        UnbondingTier {
+            unbonding_period: 86400 * 30,
+            weight: Decimal::percent(40),
+        },
+    ];
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(Some(tiers.clone()))).unwrap();
+
+    let config = CONFIG.load(deps.as_ref().storage).unwrap();
+    assert_eq!(config.protocol.adapter().unbonding_tiers(), tiers);
+    // new tiers only apply to new stakes
+    assert_eq!(
+        DELEGATED.load(deps.as_ref().storage, 86400 * 365).unwrap(),
+        Uint128::new(STAKED)
+    );
+    assert_eq!(
+        DELEGATED.may_load(deps.as_ref().storage, 86400 * 30).unwrap(),
+        None
+    );
+
+    // invalid tiers are rejected
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        from_v1_msg(Some(vec![UnbondingTier {
+            unbonding_period: 86400,
+            weight: Decimal::percent(50),
+        }])),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidUnbondingTiers {});
+}
+
+#[test]
+fn migrate_bloop_converter_from_v1() {
+    let mut deps = mock_deps_with_stake();
+    seed_loop_v1(&mut deps);
+
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    let config = CONFIG.load(deps.as_ref().storage).unwrap();
+    assert_eq!(
+        config.protocol,
+        UnderlyingProtocol::Loop(LoopStaking {
+            loop_token: Addr::unchecked("loop"),
+            loop_protocol_staking: Addr::unchecked("staking"),
+            duration: 12,
+        })
+    );
+    assert_eq!(config.btoken, Some(Addr::unchecked("btoken")));
+    assert_eq!(config.btoken_vault, Some(Addr::unchecked("vault")));
+    assert_eq!(
+        GAUGE_CONFIG.load(deps.as_ref().storage).unwrap(),
+        GaugeConfig {
+            gauge_contract: "loop_gauge".to_string(),
+            synergistic_gauge_contract: None,
+        }
+    );
+    assert_eq!(
+        DELEGATED.load(deps.as_ref().storage, 12).unwrap(),
+        Uint128::new(STAKED)
+    );
+    assert_eq!(
+        BACKING.load(deps.as_ref().storage).unwrap().deposited,
+        Uint128::new(SUPPLY)
+    );
+}
+
+#[test]
+fn migrate_refuses_unknown_and_newer_versions() {
+    // a converter that already uses the merged layout cannot run the v1 migration
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.1.0");
+    let err = migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedMigration {
+            contract: v1::WYND_CONTRACT_NAME.to_string(),
+            version: "1.1.0".to_string(),
+        }
+    );
+
+    let mut deps = mock_deps_with_stake();
+    set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "1.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedMigration {
+            contract: "crates.io:cw20-base".to_string(),
+            version: "1.0.0".to_string(),
+        }
+    );
+
+    // downgrades are refused
+    let mut deps = mock_deps_with_stake();
+    set_contract_version(deps.as_mut().storage, env!("CARGO_PKG_NAME"), "99.0.0").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap_err();
+    let version = get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.version, "99.0.0");
+
+    // v1 layouts need the v1 migration
+    let mut deps = mock_deps_with_stake();
+    set_contract_version(deps.as_mut().storage, env!("CARGO_PKG_NAME"), "1.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnsupportedMigration {
+            contract: env!("CARGO_PKG_NAME").to_string(),
+            version: "1.0.0".to_string(),
+        }
+    );
+
+    // same layout migrates
+    let mut deps = mock_deps_with_stake();
+    set_contract_version(deps.as_mut().storage, env!("CARGO_PKG_NAME"), "1.1.0").unwrap();
+    migrate(deps.as_mut(), mock_env(), MigrateMsg::FromCompatible {}).unwrap();
+}
+
+/// bToken messages of a response, in order
+fn btoken_msgs(messages: &[SubMsg]) -> Vec<Cw20ExecuteMsg> {
+    messages
+        .iter()
+        .filter_map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) if contract_addr == "btoken" => {
+                Some(from_binary(msg).unwrap())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn vault_harvest_pays_no_bounty() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    // keepers have to wait for `min_harvest`
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::WithdrawRewards {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::HarvestTooSmall(Uint128::new(100)));
+
+    // the vault harvests whatever is pending and gets all of it
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vault", &[]),
+        ExecuteMsg::WithdrawRewards {},
+    )
+    .unwrap();
+    assert_eq!(
+        btoken_msgs(&res.messages),
+        vec![
+            Cw20ExecuteMsg::Mint {
+                recipient: mock_env().contract.address.to_string(),
+                amount: Uint128::new(REWARDS),
+            },
+            Cw20ExecuteMsg::Send {
+                contract: "vault".to_string(),
+                amount: Uint128::new(REWARDS),
+                msg: to_binary(&VaultCw20HookMsg::DistributeRewards { address: None }).unwrap(),
+            },
+        ]
+    );
+}
+
+fn redeem_msg(sender: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(amount),
+        msg: to_binary(&Cw20HookMsg::Redeem {}).unwrap(),
+    })
+}
+
+#[test]
+fn redeem_then_claim_after_unbonding() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    // only the bToken can be redeemed
+    let err = execute(deps.as_mut(), mock_env(), mock_info("wynd", &[]), redeem_msg("user", 1_000)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidToken {});
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("btoken", &[]), redeem_msg("user", 1_000)).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "btoken".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn { amount: Uint128::new(1_000) }).unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_binary(&WyndDaoCoreExecuteMsg::Unbond {
+                    tokens: Uint128::new(1_000),
+                    unbonding_period: 86400 * 365,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+    assert_eq!(
+        DELEGATED.load(deps.as_ref().storage, 86400 * 365).unwrap(),
+        Uint128::new(STAKED - 1_000)
+    );
+    assert_eq!(BACKING.load(deps.as_ref().storage).unwrap().redeemed, Uint128::new(1_000));
+
+    // nothing can be claimed while the stake unbonds
+    let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), ExecuteMsg::ClaimRedeemed {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400 * 365 - 1);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), ExecuteMsg::ClaimRedeemed {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+
+    // once unbonded, the tokens are claimed from the staking module and sent on
+    env.block.time = env.block.time.plus_seconds(1);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), ExecuteMsg::ClaimRedeemed {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "staking".to_string(),
+                msg: to_binary(&WyndDaoCoreExecuteMsg::Claim {}).unwrap(),
+                funds: vec![],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "wynd".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "user".to_string(),
+                    amount: Uint128::new(1_000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // a claim is paid only once
+    let err = execute(deps.as_mut(), env, mock_info("user", &[]), ExecuteMsg::ClaimRedeemed {}).unwrap_err();
+    assert_eq!(err, ContractError::NothingToClaim {});
+}
+
+#[test]
+fn claim_redeemed_from_released_balance() {
+    let mut deps = mock_deps_with_balance(1_000);
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("btoken", &[]), redeem_msg("user", 1_000)).unwrap();
+
+    // tokens another claim already pulled out of the staking module are sent directly
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400 * 365);
+    let res = execute(deps.as_mut(), env, mock_info("user", &[]), ExecuteMsg::ClaimRedeemed {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "wynd".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "user".to_string(),
+                amount: Uint128::new(1_000),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+}
+
+#[test]
+fn loop_cannot_redeem() {
+    let loop_staking = LoopStaking {
+        loop_token: Addr::unchecked("loop"),
+        loop_protocol_staking: Addr::unchecked("staking"),
+        duration: 12,
+    };
+    let delegations = vec![Delegation {
+        unbonding_period: 12,
+        amount: Uint128::new(1_000),
+    }];
+    assert_eq!(
+        loop_staking.unbond_msgs(&delegations).unwrap_err(),
+        ContractError::RedeemNotSupported {}
+    );
+    assert_eq!(loop_staking.claim_msgs().unwrap_err(), ContractError::RedeemNotSupported {});
+
+    // a bLOOP redemption fails as a whole
+    let mut deps = mock_deps_with_stake();
+    seed_loop_v1(&mut deps);
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info("btoken", &[]), redeem_msg("user", 1_000)).unwrap_err();
+    assert_eq!(err, ContractError::RedeemNotSupported {});
+}
+
+#[test]
+fn split_puts_rounding_dust_in_the_first_tier() {
+    let tiers = vec![
+        UnbondingTier {
+            unbonding_period: 86400 * 365,
+            weight: Decimal::percent(33),
+        },
+        UnbondingTier {
+            unbonding_period: 86400 * 30,
+            weight: Decimal::percent(33),
+        },
+        UnbondingTier {
+            unbonding_period: 86400 * 7,
+            weight: Decimal::percent(34),
+        },
+    ];
+
+    // 3.3 + 3.3 + 3.4 rounds down to 9, the last token goes to the first tier
+    assert_eq!(
+        split_by_weight(&tiers, Uint128::new(10)),
+        vec![
+            Delegation {
+                unbonding_period: 86400 * 365,
+                amount: Uint128::new(4),
+            },
+            Delegation {
+                unbonding_period: 86400 * 30,
+                amount: Uint128::new(3),
+            },
+            Delegation {
+                unbonding_period: 86400 * 7,
+                amount: Uint128::new(3),
+            },
+        ]
+    );
+
+    // tiers that round to nothing are left out
+    assert_eq!(
+        split_by_weight(&tiers, Uint128::new(1)),
+        vec![Delegation {
+            unbonding_period: 86400 * 365,
+            amount: Uint128::new(1),
+        }]
+    );
+}
+
+#[test]
+fn take_delegations_drains_the_shortest_period_first() {
+    let mut deps = mock_dependencies();
+    DELEGATED.save(deps.as_mut().storage, 86400 * 365, &Uint128::new(100)).unwrap();
+    DELEGATED.save(deps.as_mut().storage, 86400 * 30, &Uint128::new(50)).unwrap();
+    DELEGATED.save(deps.as_mut().storage, 86400 * 7, &Uint128::new(20)).unwrap();
+
+    assert_eq!(
+        take_delegations(deps.as_mut().storage, Uint128::new(60)).unwrap(),
+        vec![
+            Delegation {
+                unbonding_period: 86400 * 7,
+                amount: Uint128::new(20),
+            },
+            Delegation {
+                unbonding_period: 86400 * 30,
+                amount: Uint128::new(40),
+            },
+        ]
+    );
+    assert_eq!(DELEGATED.load(deps.as_ref().storage, 86400 * 7).unwrap(), Uint128::zero());
+    assert_eq!(DELEGATED.load(deps.as_ref().storage, 86400 * 30).unwrap(), Uint128::new(10));
+    assert_eq!(DELEGATED.load(deps.as_ref().storage, 86400 * 365).unwrap(), Uint128::new(100));
+
+    // emptied periods are skipped
+    assert_eq!(
+        take_delegations(deps.as_mut().storage, Uint128::new(30)).unwrap(),
+        vec![
+            Delegation {
+                unbonding_period: 86400 * 30,
+                amount: Uint128::new(10),
+            },
+            Delegation {
+                unbonding_period: 86400 * 365,
+                amount: Uint128::new(20),
+            },
+        ]
+    );
+
+    let err = take_delegations(deps.as_mut().storage, Uint128::new(81)).unwrap_err();
+    assert_eq!(err, ContractError::InsufficientDelegation {});
+}
+
+#[test]
+fn zap_converts_only_the_swapped_amount() {
+    // 1_000 WYND are already waiting for redemption claims
+    let mut deps = mock_deps_with_balance(1_000);
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    let operations = vec![SwapOperation::SynedexSwap {
+        offer_asset_info: AssetInfo::Native("ujuno".to_string()),
+        ask_asset_info: AssetInfo::Token("wynd".to_string()),
+    }];
+
+    // the route has to end in the underlying token
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &coins(500, "ujuno")),
+        ExecuteMsg::ZapConvert {
+            operations: vec![SwapOperation::SynedexSwap {
+                offer_asset_info: AssetInfo::Native("ujuno".to_string()),
+                ask_asset_info: AssetInfo::Token("usdc".to_string()),
+            }],
+            minimum_receive: Uint128::new(400),
+            max_spread: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSwapRoute {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &coins(500, "ujuno")),
+        ExecuteMsg::ZapConvert {
+            operations: operations.clone(),
+            minimum_receive: Uint128::new(400),
+            max_spread: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "router".to_string(),
+                msg: to_binary(&MultiHopExecuteMsg::ExecuteSwapOperations {
+                    operations,
+                    minimum_receive: Some(Uint128::new(400)),
+                    receiver: None,
+                    max_spread: None,
+                    referral_address: None,
+                    referral_commission: None,
+                })
+                .unwrap(),
+                funds: coins(500, "ujuno"),
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: mock_env().contract.address.to_string(),
+                msg: to_binary(&ExecuteMsg::ConvertSwapped {
+                    recipient: "user".to_string(),
+                    prev_balance: Uint128::new(1_000),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // only the converter itself finishes a zap
+    let convert_swapped = ExecuteMsg::ConvertSwapped {
+        recipient: "user".to_string(),
+        prev_balance: Uint128::new(400),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), convert_swapped.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // what arrived on top of the previous balance is converted
+    let contract = mock_env().contract.address;
+    let res = execute(deps.as_mut(), mock_env(), mock_info(contract.as_str(), &[]), convert_swapped).unwrap();
+    assert_eq!(
+        res.attributes[..4],
+        [
+            attr("action", "stake_token"),
+            attr("token", "wynd"),
+            attr("to", "staking"),
+            attr("amount", "600"),
+        ]
+    );
+    assert_eq!(
+        btoken_msgs(&res.messages)[0],
+        Cw20ExecuteMsg::Mint {
+            recipient: "user".to_string(),
+            amount: Uint128::new(600),
+        }
+    );
+    assert_eq!(
+        BACKING.load(deps.as_ref().storage).unwrap().deposited,
+        Uint128::new(SUPPLY + 600)
+    );
+}
+
+/// `{"claim":{}}` on the airdrop contract, with optional funds
+fn airdrop_claim(funds: &[Coin]) -> CosmosMsg {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "airdrop".to_string(),
+        msg: Binary::from(br#"{"claim":{}}"#.to_vec()),
+        funds: funds.to_vec(),
+    })
+}
+
+fn schedule(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    sender: &str,
+    action: AdminAction,
+) -> Result<Response, ContractError> {
+    execute(deps.as_mut(), mock_env(), mock_info(sender, &[]), ExecuteMsg::ScheduleAction { action })
+}
+
+#[test]
+fn scheduled_actions_wait_for_the_delay() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    let msgs = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "gauge".to_string(),
+        msg: to_binary(&"reset").unwrap(),
+        funds: vec![],
+    })];
+    let action = AdminAction::ExecuteCosmosMsgs { msgs: msgs.clone() };
+
+    let err = schedule(&mut deps, "user", action.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = schedule(&mut deps, "admin", action).unwrap();
+    assert!(res.messages.is_empty());
+    let ready_at = mock_env().block.time.seconds() + 86400;
+    assert_eq!(SCHEDULED_ACTIONS.load(deps.as_ref().storage, 1).unwrap().ready_at, ready_at);
+
+    // too early
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400 - 1);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ActionNotReady(ready_at));
+
+    // ready, but only for the admin
+    env.block.time = env.block.time.plus_seconds(1);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("user", &[]), ExecuteMsg::ExecuteAction { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 1 }).unwrap();
+    assert_eq!(res.messages, msgs.into_iter().map(SubMsg::new).collect::<Vec<_>>());
+
+    // an action runs only once
+    let err = execute(deps.as_mut(), env, mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ActionNotFound(1));
+}
+
+#[test]
+fn guardian_cancels_scheduled_actions() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    let action = AdminAction::Mint {
+        recipient: "user".to_string(),
+        amount: Uint128::new(1_000),
+    };
+    schedule(&mut deps, "admin", action.clone()).unwrap();
+    schedule(&mut deps, "admin", action).unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), ExecuteMsg::CancelAction { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(deps.as_mut(), mock_env(), mock_info("guardian", &[]), ExecuteMsg::CancelAction { id: 1 }).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), ExecuteMsg::CancelAction { id: 2 }).unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400);
+    let err = execute(deps.as_mut(), env, mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ActionNotFound(1));
+    let err = execute(deps.as_mut(), mock_env(), mock_info("guardian", &[]), ExecuteMsg::CancelAction { id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::ActionNotFound(2));
+}
+
+#[test]
+fn allowlist_changes_wait_and_allowlisted_msgs_do_not() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    // nothing is allowlisted yet
+    let res = schedule(&mut deps, "admin", AdminAction::ExecuteCosmosMsgs { msgs: vec![airdrop_claim(&[])] }).unwrap();
+    assert!(res.messages.is_empty());
+    assert!(SCHEDULED_ACTIONS.has(deps.as_ref().storage, 1));
+
+    // the timelock config is itself subject to the delay
+    let allowlist = vec![AllowedMsg {
+        contract: Addr::unchecked("airdrop"),
+        msg: "claim".to_string(),
+    }];
+    schedule(
+        &mut deps,
+        "admin",
+        AdminAction::UpdateTimelockConfig {
+            delay: Some(0),
+            guardian: None,
+            allowlist: Some(allowlist.clone()),
+        },
+    )
+    .unwrap();
+    assert_eq!(TIMELOCK_CONFIG.load(deps.as_ref().storage).unwrap().delay, 86400);
+    let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::ActionNotReady(mock_env().block.time.seconds() + 86400));
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400);
+    execute(deps.as_mut(), env, mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 2 }).unwrap();
+    assert_eq!(
+        TIMELOCK_CONFIG.load(deps.as_ref().storage).unwrap(),
+        TimelockConfig {
+            delay: 0,
+            guardian: Some(Addr::unchecked("guardian")),
+            allowlist,
+        }
+    );
+
+    // allowlisted messages run right away
+    let res = schedule(&mut deps, "admin", AdminAction::ExecuteCosmosMsgs { msgs: vec![airdrop_claim(&[])] }).unwrap();
+    assert_eq!(res.messages, vec![SubMsg::new(airdrop_claim(&[]))]);
+    assert_eq!(NEXT_ACTION_ID.load(deps.as_ref().storage).unwrap(), 3);
+
+    // a message with funds, or next to one that is not allowlisted, is scheduled
+    let res = schedule(
+        &mut deps,
+        "admin",
+        AdminAction::ExecuteCosmosMsgs { msgs: vec![airdrop_claim(&coins(1, "ujuno"))] },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    let res = schedule(
+        &mut deps,
+        "admin",
+        AdminAction::ExecuteCosmosMsgs {
+            msgs: vec![
+                airdrop_claim(&[]),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "airdrop".to_string(),
+                    msg: Binary::from(br#"{"withdraw":{}}"#.to_vec()),
+                    funds: vec![],
+                }),
+            ],
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(NEXT_ACTION_ID.load(deps.as_ref().storage).unwrap(), 5);
+}
+
+#[test]
+fn admin_mints_stay_backed() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    let mint = |amount: u128| AdminAction::Mint {
+        recipient: "user".to_string(),
+        amount: Uint128::new(amount),
+    };
+    schedule(&mut deps, "admin", mint(STAKED - SUPPLY + 1)).unwrap();
+    schedule(&mut deps, "admin", mint(STAKED - SUPPLY)).unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(86400);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 1 }).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnbackedMint {
+            amount: Uint128::new(STAKED - SUPPLY + 1),
+            total_supply: Uint128::new(SUPPLY),
+            delegated: Uint128::new(STAKED),
+        }
+    );
+
+    let res = execute(deps.as_mut(), env, mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 2 }).unwrap();
+    assert_eq!(
+        btoken_msgs(&res.messages),
+        vec![Cw20ExecuteMsg::Mint {
+            recipient: "user".to_string(),
+            amount: Uint128::new(STAKED - SUPPLY),
+        }]
+    );
+    assert_eq!(BACKING.load(deps.as_ref().storage).unwrap().minted, Uint128::new(STAKED - SUPPLY));
+}
+
+#[test]
+fn raw_btoken_msgs_are_refused() {
+    let mut deps = mock_deps_with_stake();
+    seed_wynd_v1(&mut deps, "1.0.0");
+    migrate(deps.as_mut(), mock_env(), from_v1_msg(None)).unwrap();
+
+    // even allowlisted
+    TIMELOCK_CONFIG
+        .update(deps.as_mut().storage, |mut timelock_config| -> StdResult<_> {
+            timelock_config.allowlist = vec![AllowedMsg {
+                contract: Addr::unchecked("btoken"),
+                msg: "mint".to_string(),
+            }];
+            Ok(timelock_config)
+        })
+        .unwrap();
+
+    let raw_mint = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "btoken".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: "admin".to_string(),
+            amount: Uint128::new(STAKED),
+        })
+        .unwrap(),
+        funds: vec![],
+    });
+    let err = schedule(&mut deps, "admin", AdminAction::ExecuteCosmosMsgs { msgs: vec![raw_mint.clone()] }).unwrap_err();
+    assert_eq!(err, ContractError::BTokenMsgNotAllowed {});
+    let err = schedule(
+        &mut deps,
+        "admin",
+        AdminAction::ExecuteCosmosMsgs {
+            msgs: vec![CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr: "btoken".to_string(),
+                admin: "admin".to_string(),
+            })],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::BTokenMsgNotAllowed {});
+
+    // actions queued before are refused when executed
+    SCHEDULED_ACTIONS
+        .save(
+            deps.as_mut().storage,
+            1,
+            &ScheduledAction {
+                id: 1,
+                action: AdminAction::ExecuteCosmosMsgs { msgs: vec![raw_mint] },
+                ready_at: mock_env().block.time.seconds(),
+            },
+        )
+        .unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), ExecuteMsg::ExecuteAction { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::BTokenMsgNotAllowed {});
+}
@@ -0,0 +1,51 @@
+//! Storage layouts of the bWYND and bLOOP converters before they were merged into this
+//! contract. Only read by `migrate`.
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub const WYND_CONTRACT_NAME: &str = "bwynd-converter-and-staker";
+pub const LOOP_CONTRACT_NAME: &str = "bloop-converter-and-staker";
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct WyndConfig {
+    pub admin: Addr,
+    pub cw20_code_id: u64,
+    pub vault_code_id: u64,
+    pub wynd_token: Addr,
+    pub wynd_staking_module: Addr,
+    pub bwynd: Option<Addr>,
+    pub bwynd_vault: Option<Addr>,
+    pub unbonding_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct WyndGaugeConfig {
+    pub wynd_gauge_contract: String,
+    pub synergistic_wynd_gauge_contract: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct LoopConfig {
+    pub admin: Addr,
+    pub cw20_code_id: u64,
+    pub vault_code_id: u64,
+    pub loop_token: Addr,
+    pub loop_protocol_staking: Addr,
+    pub duration: u64,
+    pub bloop_token: Option<Addr>,
+    pub bloop_vault: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct LoopGaugeConfig {
+    pub loop_gauge_contract: String,
+    pub synergistic_loop_gauge_contract: Option<String>,
+}
+
+pub const WYND_CONFIG: Item<WyndConfig> = Item::new("config");
+pub const WYND_GAUGE_CONFIG: Item<WyndGaugeConfig> = Item::new("gauge_config");
+pub const LOOP_CONFIG: Item<LoopConfig> = Item::new("config");
+pub const LOOP_GAUGE_CONFIG: Item<LoopGaugeConfig> = Item::new("gauge_config");
@@ -1,5 +0,0 @@
-pub mod contract;
-pub mod msg;
-pub mod queriers;
-pub mod state;
-pub mod error;
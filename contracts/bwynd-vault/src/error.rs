@@ -1,7 +1,7 @@
-use cosmwasm_std::{Coin, OverflowError, StdError, Uint128};
+use cosmwasm_std::{Coin, ConversionOverflowError, OverflowError, StdError, Uint128};
 use thiserror::Error;
 
-use cw_controllers::{AdminError, HookError};
+use cw_controllers::{AdminError, HookError, OwnershipError, PausableError};
 use syne_curve_utils::CurveError;
 use synedex::asset::AssetInfoValidated;
 
@@ -16,9 +16,18 @@ pub enum ContractError {
     #[error("{0}")]
     Hook(#[from] HookError),
 
+    #[error("{0}")]
+    Pausable(#[from] PausableError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
     #[error("{0}")]
     Curve(#[from] CurveError),
 
+    #[error("{0}")]
+    Cw20Base(#[from] cw20_base::ContractError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -87,17 +96,44 @@ pub enum ContractError {
     #[error("No withdrawable amont")]
     NoWithdrawable {},
 
-    #[error("Previos action is not completed")]
-    InvalidAction {},
-
-    #[error("The sum of treasury and syne staking fee must be equal to total fee")]
+    #[error("The fee shares add up to more than the total fee cap")]
     InvalidFee {},
 
-    #[error("The treasury or syne staking reward fee rate is invalid")]
-    InvalidFeeRate {},
+    #[error("At most {0} fee recipients are allowed")]
+    TooManyFeeRecipients(usize),
+
+    #[error("Fee recipient label {0} is used twice")]
+    DuplicateFeeLabel(String),
+
+    #[error("SYNE distribution is not configured")]
+    SyneNotConfigured {},
+
+    #[error("Cannot change the SYNE token once it was funded")]
+    SyneTokenLocked {},
+
+    #[error("No reward stream for {0}")]
+    NoRewardStream(AssetInfoValidated),
+
+    #[error("A stake must be at least the minimum bond of {0}")]
+    BelowMinBond(Uint128),
+
+    #[error("Reward accounting overflow: {0}")]
+    RewardOverflow(OverflowError),
+
+    #[error("Reward accounting overflow: {0}")]
+    RewardConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("Cannot remove {amount} from a stake of {stake}")]
+    StakeUnderflow { stake: Uint128, amount: Uint128 },
+
+    #[error("No swap router configured")]
+    NoSwapRouter {},
+
+    #[error("Swap route must start with the staked token and end in the asked asset")]
+    InvalidSwapRoute {},
 
-    #[error("Syne staking reward distributor is not defined")]
-    InvalidDistributor {},
+    #[error("Cannot migrate from {contract} {version}")]
+    UnsupportedMigration { contract: String, version: String },
 }
 
 impl From<OverflowError> for ContractError {
@@ -26,4 +26,13 @@ pub mod state;
 
 pub mod queriers;
 
+/// messages sent to the hooks when a stake changes
+pub mod hook;
+
+/// state layouts of older versions, read by the migration
+mod v1;
+
+#[cfg(test)]
+mod testing;
+
 pub use crate::error::ContractError;
@@ -1,71 +1,176 @@
 use cosmwasm_schema::cw_serde;
+use cw20::Cw20ReceiveMsg;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_controllers::{Admin};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Decimal256, Empty, Uint128};
+use cw_controllers::{Admin, Claims, Hooks, Ownership, Pausable};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use synedex::asset::AssetInfoValidated;
+use synedex::multi_hop::SwapOperation;
+use synedex::oracle::TimeBuffer;
 
 
 #[cw_serde]
 pub struct Config {
-    pub admin: Addr,
     /// address of cw20 contract token to stake
     pub token: Addr,
     pub bwynd_converter_and_staker: Addr,
     pub min_bond: Uint128,
     pub wynd_staking_module: Addr,
-    pub treasury_wallet: Option<Addr>,
-    pub treasury_withdrawer: Option<Addr>,
-    pub syne_staking_reward_distributor: Option<Addr>,
-    pub treasury_fee: Decimal,
-    pub syne_staking_fee: Decimal,
+    /// Take their share of every reward distribution before the stakers
+    pub fee_recipients: Vec<FeeRecipient>,
+    /// Most the shares of all fee recipients may add up to
     pub total_fee_cap: Decimal,
-    pub treasury_fee_limit: Decimal,
+    /// Seconds unstaked tokens wait before they can be claimed, released right away when 0
+    #[serde(default)]
+    pub unbonding_period: u64,
+    /// Multi-hop router `WithdrawRewardsAs` swaps the rewards through
+    #[serde(default)]
+    pub swap_router: Option<Addr>,
+}
 
+#[cw_serde]
+pub struct FeeRecipient {
+    pub address: Addr,
+    pub share: Decimal,
+    /// Tells the recipients apart in queries and events
+    pub label: String,
 }
 
 pub const ADMIN: Admin = Admin::new("admin");
+pub const PAUSABLE: Pausable = Pausable::new("pausable");
+pub const OWNERSHIP_PROPOSAL: Ownership = Ownership::new("ownership_proposal");
 pub const CONFIG: Item<Config> = Item::new("config");
+/// Contracts notified with a `MemberChangedHookMsg` whenever a stake changes
+pub const HOOKS: Hooks = Hooks::new("hooks");
 
-#[derive(Default, Serialize, Deserialize)]
+/// `power` is the reward per bonded share distributed so far, kept at 18 decimals on 256 bits so
+/// large supplies neither overflow nor lose the rewards of small stakes
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct TokenInfo {
     pub staked: Uint128,
-    pub pending_treasury_rewards: Uint128,
-    pub pending_syne_staking_rewards: Uint128,
-    pub power: Decimal,
+    pub power: Decimal256,
+    /// Part of `staked` at or above the minimum bond, the stake rewards are shared over
+    #[serde(default)]
+    pub bonded: Uint128,
 }
 
-#[derive(Default, Serialize, Deserialize)]
+/// `power_diff` is `TokenInfo::power` when the rewards of the stake were last settled
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct StakingInfo {
     pub stake: Uint128,
-    pub power_diff: Decimal,
+    pub power_diff: Decimal256,
 }
 
+/// Route of `ExecuteMsg::WithdrawRewardsAs` through the swap router
+#[derive(Serialize, Deserialize)]
+pub struct RewardSwap {
+    pub operations: Vec<SwapOperation>,
+    pub minimum_receive: Uint128,
+    pub max_spread: Option<Decimal>,
+}
+
+/// What a user asked for while the converter still held undistributed rewards
 #[derive(Serialize, Deserialize)]
 pub enum RewardAction {
-    NoAction {},
     Reward {
         address: Addr,
+        /// Where the rewards are sent, `address` when unset
+        #[serde(default)]
+        receiver: Option<Addr>,
+        /// Swaps the rewards in the staked token before they are sent
+        #[serde(default)]
+        swap: Option<RewardSwap>,
     },
     Stake {
         address: Addr,
         amount: Uint128,
     },
-    TreasuryWithdraw {
-        amount: Option<Uint128>,
-    },
-    SyneStakingRewardWithdraw {
-        amount: Option<Uint128>,
-    },
+    /// Pays the pending balances of all fee recipients
+    FlushFees {},
     Unstake {
         address: Addr,
         amount: Uint128,
     },
+    /// Adds the pending rewards of the next batch of auto-compounding stakers to their stakes
+    Compound {},
+    /// Moves shares, `receive` is sent to `recipient` afterwards for a `Send`
+    Transfer {
+        owner: Addr,
+        recipient: Addr,
+        amount: Uint128,
+        receive: Option<Cw20ReceiveMsg>,
+    },
 }
 
-pub const TOTAL_STAKED: Item<TokenInfo> = Item::new("total_staked");
-pub const STAKE: Map<&Addr, StakingInfo> = Map::new("stake");
-pub const REWARD_ACTION: Item<RewardAction> = Item::new("reward_action");
+/// Snapshotted every block, so `TotalPowerAtHeight` can look back
+pub const TOTAL_STAKED: SnapshotItem<TokenInfo> = SnapshotItem::new(
+    "total_staked",
+    "total_staked__checkpoints",
+    "total_staked__changelog",
+    Strategy::EveryBlock,
+);
+/// Snapshotted every block, so `VotingPowerAtHeight` can look back
+pub const STAKE: SnapshotMap<&Addr, StakingInfo> = SnapshotMap::new(
+    "stake",
+    "stake__checkpoints",
+    "stake__changelog",
+    Strategy::EveryBlock,
+);
+/// Running totals shown by `QueryMsg::Stats`
+#[derive(Default, Serialize, Deserialize)]
+pub struct VaultStats {
+    /// Addresses with a stake
+    pub stakers: u64,
+    /// Rewards distributed to the stakers, after fees
+    pub distributed: Uint128,
+    /// Rewards taken by the fee recipients
+    #[serde(default)]
+    pub fees: Uint128,
+}
+
+pub const STATS: Item<VaultStats> = Item::new("stats");
+
+/// `VaultStats::distributed` and `TokenInfo::power` after a distribution
+#[cw_serde]
+pub struct RewardPoint {
+    pub distributed: Uint128,
+    pub power: Decimal256,
+}
+
+pub const HOUR: u64 = 60 * 60;
+pub const HALF_DAY: u64 = 12 * HOUR;
+pub const DAY: u64 = 24 * HOUR;
+
+/// For each reward history, the last time a point was recorded in it
+#[cw_serde]
+#[derive(Default)]
+pub struct RewardsRecorded {
+    pub hours: u64,
+    pub half_days: u64,
+    pub days: u64,
+}
+
+pub const REWARDS_RECORDED: Item<RewardsRecorded> = Item::new("rewards_recorded");
+
+// each buffer spans one bucket more than its window, so a point from before the window is kept
+pub const DAY_REWARDS: TimeBuffer<RewardPoint, HOUR, 25> = TimeBuffer::new("day_rewards", "day_rewards_start");
+pub const WEEK_REWARDS: TimeBuffer<RewardPoint, HALF_DAY, 15> = TimeBuffer::new("week_rewards", "week_rewards_start");
+pub const MONTH_REWARDS: TimeBuffer<RewardPoint, DAY, 31> = TimeBuffer::new("month_rewards", "month_rewards_start");
+
+/// Stakers whose rewards are added to their stake instead of being sent to them
+pub const AUTO_COMPOUND: Map<&Addr, Empty> = Map::new("auto_compound");
+/// Last staker compounded by `Compound {}`, the next call continues after it
+pub const COMPOUND_CURSOR: Item<Addr> = Item::new("compound_cursor");
+/// Address allowed to withdraw the rewards of a staker, besides the staker itself
+pub const WITHDRAW_DELEGATES: Map<&Addr, Addr> = Map::new("withdraw_delegates");
+/// Fees taken but not sent yet, by recipient address. Outlives the recipient being removed.
+pub const PENDING_FEES: Map<&Addr, Uint128> = Map::new("pending_fees");
+/// Unstaked tokens waiting for the unbonding period
+pub const CLAIMS: Claims = Claims::new("claims");
+/// Actions waiting for the reply of the harvest submessage with the same id
+pub const PENDING_ACTIONS: Map<u64, RewardAction> = Map::new("pending_actions");
+pub const NEXT_HARVEST_ID: Item<u64> = Item::new("next_harvest_id");
 
 // for syne reward upgrading
 #[cw_serde]
@@ -73,13 +178,34 @@ pub struct SyneDistributionConfig {
     pub syne_addr: String,
     pub distribution_per_day: u64,
 }
+/// SYNE emitted to the stakers. `pending` is funded but not yet emitted, `power` is the
+/// SYNE earned per staked token.
 #[cw_serde]
+#[derive(Default)]
 pub struct CurrentSyneDistribution {
     pub total_distributed: Uint128,
     pub pending: Uint128,
     pub last_distributed_time: u64,
-    pub power: Decimal,
+    pub power: Decimal256,
 }
 
 pub const SYNE_DISTRIBUTION_CONFIG: Item<SyneDistributionConfig> = Item::new("syne_distribution_config");
 pub const CURRENT_SYNE_DISTRIBUTION: Item<CurrentSyneDistribution> = Item::new("current_syne_distribution");
+/// `CurrentSyneDistribution::power` when the SYNE of a staker was last settled
+pub const SYNE_POWER_DIFF: Map<&Addr, Decimal256> = Map::new("syne_power_diff");
+
+/// Rewards in a partner asset, emitted evenly until `end`
+#[cw_serde]
+pub struct RewardStream {
+    /// rewards earned per staked token
+    pub power: Decimal256,
+    /// funded but not emitted yet
+    pub remaining: Uint128,
+    pub total_distributed: Uint128,
+    pub last_update: u64,
+    pub end: u64,
+}
+
+pub const REWARD_STREAMS: Map<&AssetInfoValidated, RewardStream> = Map::new("reward_streams");
+/// `RewardStream::power` when the rewards of a staker in that asset were last settled
+pub const STREAM_POWER_DIFF: Map<(&Addr, &AssetInfoValidated), Decimal256> = Map::new("stream_power_diff");
@@ -0,0 +1,97 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+use crate::state;
+
+/// Config of v1.0, which also stored the admin
+#[cw_serde]
+pub struct Config {
+    pub admin: Addr,
+    pub token: Addr,
+    pub bwynd_converter_and_staker: Addr,
+    pub min_bond: Uint128,
+    pub wynd_staking_module: Addr,
+    pub treasury_wallet: Option<Addr>,
+    pub treasury_withdrawer: Option<Addr>,
+    pub syne_staking_reward_distributor: Option<Addr>,
+    pub treasury_fee: Decimal,
+    pub syne_staking_fee: Decimal,
+    pub total_fee_cap: Decimal,
+    pub treasury_fee_limit: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+impl Config {
+    /// Where the treasury and the SYNE staking fee went. `admin` stands in for an address
+    /// that was never set, as the fees waited for it.
+    pub fn fee_addresses(&self, admin: &Addr) -> (Addr, Addr) {
+        (
+            self.treasury_wallet.clone().unwrap_or_else(|| admin.clone()),
+            self.syne_staking_reward_distributor.clone().unwrap_or_else(|| admin.clone()),
+        )
+    }
+
+    /// Turns both fees into fee recipients
+    pub fn into_config(self, admin: &Addr) -> state::Config {
+        let (treasury, syne_staking) = self.fee_addresses(admin);
+        let fee_recipients = vec![
+            (treasury, self.treasury_fee, "treasury"),
+            (syne_staking, self.syne_staking_fee, "syne_staking"),
+        ]
+        .into_iter()
+        .filter(|(_, share, _)| !share.is_zero())
+        .map(|(address, share, label)| state::FeeRecipient { address, share, label: label.to_string() })
+        .collect();
+
+        state::Config {
+            token: self.token,
+            bwynd_converter_and_staker: self.bwynd_converter_and_staker,
+            min_bond: self.min_bond,
+            wynd_staking_module: self.wynd_staking_module,
+            fee_recipients,
+            total_fee_cap: self.total_fee_cap,
+            unbonding_period: 0,
+            swap_router: None,
+        }
+    }
+}
+
+/// Total stake of v1.0, which also kept the fees taken
+#[derive(Default, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub staked: Uint128,
+    pub pending_treasury_rewards: Uint128,
+    pub pending_syne_staking_rewards: Uint128,
+    pub power: Decimal,
+}
+
+/// Shares its key with the primary value of the current snapshot item
+pub const TOTAL_STAKED: Item<TokenInfo> = Item::new("total_staked");
+
+/// The single slot holding the action of an ongoing harvest in v1.0
+#[derive(Serialize, Deserialize)]
+pub enum RewardAction {
+    NoAction {},
+    Reward {
+        address: Addr,
+    },
+    Stake {
+        address: Addr,
+        amount: Uint128,
+    },
+    TreasuryWithdraw {
+        amount: Option<Uint128>,
+    },
+    SyneStakingRewardWithdraw {
+        amount: Option<Uint128>,
+    },
+    Unstake {
+        address: Addr,
+        amount: Uint128,
+    },
+}
+
+pub const REWARD_ACTION: Item<RewardAction> = Item::new("reward_action");
@@ -1,3 +1,4 @@
+use crate::error::ContractError;
 use crate::queriers::{query_loop_farm_stakable_token, query_loop_farm_pending_rewards, query_loop_farm_reward_in_pool, query_loop_farm_distribution_wait_time, query_loop_farm_lock_time_frame, query_loop_farm_pool_rewards, query_loop_farm_last_distribution_time};
 //use crate::response::MsgInstantiateContractResponse;
 use crate::state::{
@@ -7,7 +8,7 @@ use crate::state::{
     TOTAL_ACCUMULATED_DISTRIBUTED_AMOUNT_IN_POOL_MAP, TOTAL_REWARDS_IN_POOL, TOTAL_STAKED,
     UNCLAIMED_DISTRIBUTED_TOKEN_AMOUNT_MAP, USER_AUTO_COMPOUND_SUBSCRIPTION_MAP,
     USER_REWARD_INFO_MAP, USER_REWARD_STARTING_TIME_MAP,
-    USER_STAKED_AMOUNT, POOL_REWARD_WEIGHT_MAP, CurrentStakeInfo, CURRENT_STAKE_INFO, CURRENT_UNSTAKE_INFO, CurrentUnstakeInfo, CURRENT_CLAIM_REWARD_INFO, CurrentClaimRewardInfo, TOTAL_REWARDS, PENDING_REWARDS, USER_ACTION, UserAction, TOTAL_REWARDS_WEIGHT, TREASURY_REWARDS, TreasuryRewardsInfo,
+    USER_STAKED_AMOUNT, POOL_REWARD_WEIGHT_MAP, CurrentStakeInfo, CURRENT_STAKE_INFO, CURRENT_UNSTAKE_INFO, CurrentUnstakeInfo, CURRENT_CLAIM_REWARD_INFO, CurrentClaimRewardInfo, TOTAL_REWARDS, PENDING_REWARDS, USER_ACTION, UserAction, TOTAL_REWARDS_WEIGHT, TREASURY_REWARDS, TreasuryRewardsInfo, OWNERSHIP_PROPOSAL, PAUSABLE,
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -19,6 +20,7 @@ use cosmwasm_std::{
 use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
+use cw_controllers::PausableOperation;
 use cw_storage_plus::Bound;
 use syneswap::asset::{StakeableToken};
 use syneswap::factory::MigrateMsg;
@@ -68,9 +70,37 @@ pub fn instantiate(
 
 //Execute the handle messages.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, env, info, owner),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_propose_new_owner(
+                deps,
+                info,
+                &env,
+                &config.owner,
+                owner,
+                expires_in,
+            )?)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_drop_ownership_proposal(deps, info, &config.owner)?)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            Ok(OWNERSHIP_PROPOSAL.execute_claim_ownership(deps, info, &env, |deps, owner| {
+                CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+                    config.owner = owner;
+                    Ok(config)
+                })?;
+                Ok(())
+            })?)
+        }
         ExecuteMsg::UpdateTreasuryAddr {
             treasury_addr,
         } => execute_update_treasury_addr(deps, info, treasury_addr),
@@ -79,9 +109,11 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         }
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::ClaimReward { pool_address, start_after } => {
+            PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Claim)?;
             execute_claim_reward(deps, env, info, pool_address, start_after)
         }
         ExecuteMsg::UnstakeAndClaim { pool_address, amount, start_after } => {
+            PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Withdraw)?;
             execute_unstake_and_claim(deps, env, info, pool_address, amount, start_after)
         }
         ExecuteMsg::AddStakeableToken { pool_address, liquidity_token } => {
@@ -96,6 +128,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::WithdrawTreasuryReward { token, amount } => {
             execute_withdraw_treasury_reward(deps, env, info, token, amount)
         }
+        ExecuteMsg::Pause { operations } => {
+            let is_owner = info.sender == CONFIG.load(deps.storage)?.owner;
+            Ok(PAUSABLE.execute_pause(deps, info, is_owner, operations)?)
+        }
+        ExecuteMsg::Unpause { operations } => {
+            let is_owner = info.sender == CONFIG.load(deps.storage)?.owner;
+            Ok(PAUSABLE.execute_unpause(deps, info, is_owner, operations)?)
+        }
+        ExecuteMsg::UpdateGuardian { guardian } => {
+            let is_owner = info.sender == CONFIG.load(deps.storage)?.owner;
+            let guardian = guardian.map(|g| deps.api.addr_validate(&g)).transpose()?;
+            Ok(PAUSABLE.execute_update_guardian(deps, info, is_owner, guardian)?)
+        }
     }
 }
 
@@ -105,7 +150,7 @@ pub fn receive_cw20(
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let pool_contract_addr = info.sender;
     match from_binary(&cw20_msg.msg) {
         Ok(Cw20HookMsg::Stake { start_after }) => {
@@ -113,6 +158,7 @@ pub fn receive_cw20(
                 .may_load(deps.storage, pool_contract_addr.to_string())?
                 .is_some()
             {
+                PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Deposit)?;
                 execute_stake(
                     deps,
                     env,
@@ -122,7 +168,7 @@ pub fn receive_cw20(
                     start_after
                 )
             } else {
-                Err(StdError::generic_err("Incorrect Asset Provided"))
+                Err(StdError::generic_err("Incorrect Asset Provided").into())
             }
         }
 
@@ -168,46 +214,21 @@ pub fn receive_cw20(
         //         Err(StdError::generic_err("Incorrect Asset Provided"))
         //     }
         // }
-        Err(_err) => Err(StdError::generic_err("Unsuccessful")),
+        Err(_err) => Err(StdError::generic_err("Unsuccessful").into()),
     }
 }
 
-/// Only owner can execute it. To update the owner address
-pub fn execute_update_config(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    owner_provided: Option<String>,
-) -> StdResult<Response> {
-    let mut config: Config = CONFIG.load(deps.storage)?;
-
-    // Permission check
-    if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
-    }
-    if let Some(owner) = owner_provided {
-        // Validate address format
-        let _ = deps.api.addr_validate(&owner)?;
-
-        config.owner = deps.api.addr_validate(&owner)?;
-    }
-
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("action", "update_config"))
-}
-
 /// Only owner can execute it. To update the treasury address
 pub fn execute_update_treasury_addr(
     deps: DepsMut,
     info: MessageInfo,
     treasury_addr: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // Permission check
     if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
     config.treasury_addr = deps.api.addr_validate(&treasury_addr)?;
 
@@ -221,12 +242,12 @@ pub fn execute_update_treasury_fee(
     deps: DepsMut,
     info: MessageInfo,
     treasury_fee: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // Permission check
     if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     config.treasury_fee = treasury_fee;
@@ -244,7 +265,7 @@ pub fn execute_stake(
     pool_address: String,
     amount: Uint128,
     start_after: Option<String>
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
     USER_ACTION.save(deps.storage, &UserAction {
@@ -279,19 +300,19 @@ pub fn execute_add_stakeable_token(
     info: MessageInfo,
     pool_address: String,
     liquidity_token: String
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
     // Permission check
     if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     if STAKEABLE_INFOS
         .may_load(deps.storage, pool_address.to_string())?
         .is_some()
     {
-        return Err(StdError::generic_err("Token already exists in list"));
+        return Err(StdError::generic_err("Token already exists in list").into());
     }
 
     let stakeable_token = StakeableToken {
@@ -318,12 +339,12 @@ pub fn execute_add_stakeable_tokens(
     info: MessageInfo,
     pool_addresses: Vec<String>,
     liquidity_tokens: Vec<String>
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
     // Permission check
     if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     ensure_eq!(pool_addresses.len(), liquidity_tokens.len(), StdError::generic_err("length mismatch"));
@@ -335,7 +356,7 @@ pub fn execute_add_stakeable_tokens(
             .may_load(deps.storage, pool_address.to_string())?
             .is_some()
         {
-            return Err(StdError::generic_err("Token already exists in list"));
+            return Err(StdError::generic_err("Token already exists in list").into());
         }
     
         let stakeable_token = StakeableToken {
@@ -364,7 +385,7 @@ pub fn execute_unstake_and_claim(
     pool_address: String,
     amount: Uint128,
     start_after: Option<String>
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if STAKEABLE_INFOS
         .may_load(deps.storage, pool_address.to_string())?
@@ -394,7 +415,7 @@ pub fn execute_unstake_and_claim(
         Ok(Response::new()
             .add_submessage(sub_msg))
     } else {
-        Err(StdError::generic_err("Incorrect Asset Provided"))
+        Err(StdError::generic_err("Incorrect Asset Provided").into())
     }
 }
 
@@ -405,7 +426,7 @@ pub fn execute_claim_reward(
     info: MessageInfo,
     pool_address: String,
     start_after: Option<String>
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
     USER_ACTION.save(deps.storage, &UserAction {
@@ -439,14 +460,14 @@ pub fn execute_distribute_by_limit(
     _info: MessageInfo,
     start_after: Option<String>,
     limit: Option<u32>
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if let Some(start_after) = start_after.clone() {
         if STAKEABLE_INFOS
             .may_load(deps.storage, start_after)?
             .is_some()
         {
-            return Err(StdError::generic_err("Token already exists in list"));
+            return Err(StdError::generic_err("Token already exists in list").into());
         }
     }
     Ok(
@@ -468,7 +489,7 @@ pub fn execute_withdraw_treasury_reward(
     info: MessageInfo,
     token: String,
     amount: Uint128
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let mut treasury_reward = TREASURY_REWARDS.load(deps.storage, token.clone()).unwrap_or(Uint128::zero());
     ensure_eq!(config.treasury_addr, info.sender, StdError::generic_err("Unauthorized"));
@@ -489,6 +510,10 @@ pub fn execute_withdraw_treasury_reward(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::IsPaused {} => to_binary(&PAUSABLE.query_is_paused(deps)?),
+        QueryMsg::OwnershipProposal {} => {
+            to_binary(&OWNERSHIP_PROPOSAL.query_ownership_proposal(deps)?)
+        }
         QueryMsg::QueryTreasuryAddress {} => to_binary(&query_treasury_address(deps)?),
         QueryMsg::QueryTreasuryFee {} => to_binary(&query_treasury_fee(deps)?),
         QueryMsg::QueryFeeMultiplier {} => to_binary(&query_fee_multiplier(deps)?),
@@ -1,5 +1,9 @@
 pub mod contract;
+pub mod error;
 //pub mod math;
 mod parse_reply;
 pub mod state;
 pub mod queriers;
+
+#[cfg(test)]
+mod testing;
@@ -2,6 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Uint128, Addr, Decimal};
+use cw_controllers::{Ownership, Pausable};
 use cw_storage_plus::{Item, Map};
 use syneswap::asset::StakeableToken;
 
@@ -59,6 +60,8 @@ pub struct TreasuryRewardsInfo {
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const PAUSABLE: Pausable = Pausable::new("pausable");
+pub const OWNERSHIP_PROPOSAL: Ownership = Ownership::new("ownership_proposal");
 pub const STAKEABLE_INFOS: Map<String, StakeableToken> = Map::new("stakeableInfos");
 pub const UNCLAIMED_DISTRIBUTED_TOKEN_AMOUNT_MAP: Map<String, Uint128> =
     Map::new("unclaimedDistributedTokenAmountMap");
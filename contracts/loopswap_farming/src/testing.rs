@@ -0,0 +1,189 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{to_binary, Addr, OwnedDeps, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw_controllers::{PausableError, PausableOperation};
+use syneswap::farming::{Cw20HookMsg, ExecuteMsg, InstantiateMsg};
+
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+use crate::state::CONFIG;
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            treasury_addr: "treasury".to_string(),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::AddStakeableToken {
+            pool_address: "pool".to_string(),
+            liquidity_token: "lp".to_string(),
+        },
+    )
+    .unwrap();
+    deps
+}
+
+/// Runs the three messages guarded by the pause flags.
+fn guarded_msgs(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+) -> Vec<Result<(), ContractError>> {
+    let stake = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "user".to_string(),
+        amount: Uint128::new(100),
+        msg: to_binary(&Cw20HookMsg::Stake { start_after: None }).unwrap(),
+    });
+    let unstake = ExecuteMsg::UnstakeAndClaim {
+        pool_address: "pool".to_string(),
+        amount: Uint128::new(100),
+        start_after: None,
+    };
+    let claim = ExecuteMsg::ClaimReward {
+        pool_address: "pool".to_string(),
+        start_after: None,
+    };
+    vec![
+        execute(deps.as_mut(), mock_env(), mock_info("pool", &[]), stake).map(|_| ()),
+        execute(deps.as_mut(), mock_env(), mock_info("user", &[]), unstake).map(|_| ()),
+        execute(deps.as_mut(), mock_env(), mock_info("user", &[]), claim).map(|_| ()),
+    ]
+}
+
+const ALL: [PausableOperation; 3] = [
+    PausableOperation::Deposit,
+    PausableOperation::Withdraw,
+    PausableOperation::Claim,
+];
+
+#[test]
+fn paused_operations_are_rejected_until_unpaused() {
+    let mut deps = setup();
+    assert_eq!(guarded_msgs(&mut deps), vec![Ok(()), Ok(()), Ok(())]);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::Pause { operations: ALL.to_vec() },
+    )
+    .unwrap();
+    assert_eq!(
+        guarded_msgs(&mut deps),
+        vec![
+            Err(PausableError::Paused(PausableOperation::Deposit).into()),
+            Err(PausableError::Paused(PausableOperation::Withdraw).into()),
+            Err(PausableError::Paused(PausableOperation::Claim).into()),
+        ]
+    );
+
+    // operations resume one by one
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::Unpause {
+            operations: vec![PausableOperation::Claim],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        guarded_msgs(&mut deps),
+        vec![
+            Err(PausableError::Paused(PausableOperation::Deposit).into()),
+            Err(PausableError::Paused(PausableOperation::Withdraw).into()),
+            Ok(()),
+        ]
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::Unpause { operations: ALL.to_vec() },
+    )
+    .unwrap();
+    assert_eq!(guarded_msgs(&mut deps), vec![Ok(()), Ok(()), Ok(())]);
+}
+
+#[test]
+fn only_owner_and_guardian_pause() {
+    let mut deps = setup();
+
+    let pause = ExecuteMsg::Pause { operations: ALL.to_vec() };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), pause.clone()).unwrap_err();
+    assert_eq!(err, PausableError::NotGuardian {}.into());
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user", &[]),
+        ExecuteMsg::UpdateGuardian {
+            guardian: Some("user".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, PausableError::NotAdmin {}.into());
+    assert_eq!(guarded_msgs(&mut deps), vec![Ok(()), Ok(()), Ok(())]);
+
+    // the guardian can halt but not resume
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateGuardian {
+            guardian: Some("guardian".to_string()),
+        },
+    )
+    .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("guardian", &[]), pause).unwrap();
+    let unpause = ExecuteMsg::Unpause { operations: ALL.to_vec() };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("guardian", &[]), unpause.clone()).unwrap_err();
+    assert_eq!(err, PausableError::NotAdmin {}.into());
+    let err = execute(deps.as_mut(), mock_env(), mock_info("user", &[]), unpause).unwrap_err();
+    assert_eq!(err, PausableError::NotAdmin {}.into());
+    assert_eq!(
+        guarded_msgs(&mut deps),
+        vec![
+            Err(PausableError::Paused(PausableOperation::Deposit).into()),
+            Err(PausableError::Paused(PausableOperation::Withdraw).into()),
+            Err(PausableError::Paused(PausableOperation::Claim).into()),
+        ]
+    );
+}
+
+#[test]
+fn claimed_ownership_moves_the_config_owner() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::ProposeNewOwner {
+            owner: "new_owner".to_string(),
+            expires_in: 100,
+        },
+    )
+    .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("new_owner", &[]), ExecuteMsg::ClaimOwnership {}).unwrap();
+    assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap().owner, Addr::unchecked("new_owner"));
+
+    // the previous owner lost its rights
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::Pause { operations: ALL.to_vec() },
+    )
+    .unwrap_err();
+    assert_eq!(err, PausableError::NotGuardian {}.into());
+}
@@ -15,7 +15,7 @@ use crate::msg::{
 };
 use crate::state::{
     fetch_last_id, update_tally, votes, Config, Gauge, GaugeId, CONFIG, GAUGES, OPTION_BY_POINTS,
-    TALLY, TOTAL_CAST,
+    OWNERSHIP_PROPOSAL, TALLY, TOTAL_CAST,
 };
 use crate::error::ContractError;
 
@@ -93,6 +93,30 @@ pub fn execute(
             execute::place_votes(deps, env, info.sender, gauge, votes)
         }
         ExecuteMsg::Execute { gauge } => execute::execute(deps, env, gauge),
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_propose_new_owner(
+                deps,
+                info,
+                &env,
+                &config.owner,
+                owner,
+                expires_in,
+            )?)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_drop_ownership_proposal(deps, info, &config.owner)?)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            Ok(OWNERSHIP_PROPOSAL.execute_claim_ownership(deps, info, &env, |deps, owner| {
+                CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+                    config.owner = owner;
+                    Ok(config)
+                })?;
+                Ok(())
+            })?)
+        }
     }
 }
 
@@ -721,6 +745,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
         )?)?),
         QueryMsg::GaugeVersion { gauge } => Ok(to_binary(&query::gauge_version(deps, gauge)?)?),
+        QueryMsg::OwnershipProposal {} => {
+            to_binary(&OWNERSHIP_PROPOSAL.query_ownership_proposal(deps)?)
+        }
     }
 }
 
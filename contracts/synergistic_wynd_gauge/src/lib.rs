@@ -5,4 +5,7 @@ pub mod msg;
 pub mod state;
 pub mod queriers;
 
+#[cfg(test)]
+mod testing;
+
 pub use crate::error::ContractError;
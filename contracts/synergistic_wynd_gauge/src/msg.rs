@@ -69,6 +69,12 @@ pub enum ExecuteMsg {
     },
     /// Takes a sample of the current tally and execute the proper messages to make it work
     Execute { gauge: u64 },
+    /// Proposes a new owner, who has `expires_in` seconds to claim the ownership
+    ProposeNewOwner { owner: String, expires_in: u64 },
+    /// Removes the pending ownership proposal
+    DropOwnershipProposal {},
+    /// Makes the proposed owner the new owner
+    ClaimOwnership {},
 }
 
 #[cw_serde]
@@ -123,7 +129,9 @@ pub enum QueryMsg {
     #[returns(GaugeVersionResponse)]
     GaugeVersion {
         gauge: u64
-    }
+    },
+    #[returns(cw_controllers::OwnershipProposalResponse)]
+    OwnershipProposal {},
 }
 
 /// Information about one gauge
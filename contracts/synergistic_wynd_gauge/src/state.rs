@@ -1,5 +1,6 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, StdResult, Storage, Uint128};
+use cw_controllers::Ownership;
 use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use cw_utils::maybe_addr;
 
@@ -10,6 +11,7 @@ pub type GaugeId = u64;
 pub type Epoch = u128;
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const OWNERSHIP_PROPOSAL: Ownership = Ownership::new("ownership_proposal");
 pub const GAUGES: Map<GaugeId, Gauge> = Map::new("gauges");
 const LAST_ID: Item<GaugeId> = Item::new("last_id");
 pub const LAST_EPOCH: Map<GaugeId, Epoch> = Map::new("last_epoch");
@@ -0,0 +1,43 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{Addr, OwnedDeps};
+use cw_controllers::OwnershipError;
+
+use crate::contract::{execute, instantiate};
+use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::state::CONFIG;
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("dao", &[]),
+        InstantiateMsg {
+            voting_powers: "voting_powers".to_string(),
+            owner: "owner".to_string(),
+            wynd_staker: "wynd_staker".to_string(),
+            gauges: None,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+#[test]
+fn claimed_ownership_moves_the_config_owner() {
+    let mut deps = setup();
+
+    let propose = |owner: &str| ExecuteMsg::ProposeNewOwner {
+        owner: owner.to_string(),
+        expires_in: 100,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), propose("new_owner")).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("new_owner", &[]), ExecuteMsg::ClaimOwnership {}).unwrap();
+    assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap().owner, Addr::unchecked("new_owner"));
+
+    // the previous owner lost its rights
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), propose("owner")).unwrap_err();
+    assert_eq!(err, OwnershipError::Unauthorized {}.into());
+}
@@ -2,11 +2,12 @@ use crate::minter::{
     execute_mint, query_balance, query_balance_by_duration, query_minter, query_token_info,
     query_total_balance, update_token_info,
 };
+use crate::error::ContractError;
 use crate::msg::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, MigrateMsg};
 use crate::state::{
     Config, SynePowerIndex, PoolRewardIndex, RewardInfo, UserInfo, UserRewardResponse, CONFIG, DISTRIBUTION_REWARD, SYNE_POWER_DATE_WISE_MAP, MINT_TIME,
     REWARD_INDEX, TOTAL_REWARD, TOTAL_REWARD_IN_CONTRACT, TOTAL_STAKED_DURATION_WISE,
-    USER_REWARD_INFO,
+    USER_REWARD_INFO, OWNERSHIP_PROPOSAL, PAUSABLE, SECOND_OWNERSHIP_PROPOSAL,
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
@@ -18,6 +19,7 @@ use cw2::set_contract_version;
 use cw20_base::enumerable::query_all_accounts;
 
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_controllers::PausableOperation;
 use cw_storage_plus::Bound;
 const REWARD_CALC_UNIT: Uint128 = Uint128::new(1000000000000u128);
 
@@ -77,11 +79,70 @@ pub fn instantiate(
 
 //Execute the handle messages.
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, env, info, owner),
-        ExecuteMsg::UpdateSecondOwner { second_owner } => {
-            execute_update_second_owner(deps, env, info, second_owner)
+        ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_propose_new_owner(
+                deps,
+                info,
+                &env,
+                &config.owner_addr,
+                owner,
+                expires_in,
+            )?)
+        }
+        ExecuteMsg::DropOwnershipProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(OWNERSHIP_PROPOSAL.execute_drop_ownership_proposal(deps, info, &config.owner_addr)?)
+        }
+        ExecuteMsg::ClaimOwnership {} => {
+            Ok(OWNERSHIP_PROPOSAL.execute_claim_ownership(deps, info, &env, |deps, owner| {
+                CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+                    config.owner_addr = owner;
+                    Ok(config)
+                })?;
+                Ok(())
+            })?)
+        }
+        // the second owner is handed over like the owner, proposed by the owner
+        ExecuteMsg::ProposeNewSecondOwner { second_owner, expires_in } => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(SECOND_OWNERSHIP_PROPOSAL.execute_propose_new_owner(
+                deps,
+                info,
+                &env,
+                &config.owner_addr,
+                second_owner,
+                expires_in,
+            )?)
+        }
+        ExecuteMsg::DropSecondOwnerProposal {} => {
+            let config = CONFIG.load(deps.storage)?;
+            Ok(SECOND_OWNERSHIP_PROPOSAL.execute_drop_ownership_proposal(
+                deps,
+                info,
+                &config.owner_addr,
+            )?)
+        }
+        ExecuteMsg::ClaimSecondOwnership {} => {
+            Ok(SECOND_OWNERSHIP_PROPOSAL.execute_claim_ownership(
+                deps,
+                info,
+                &env,
+                |deps, second_owner| {
+                    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+                        config.second_owner = Some(second_owner.to_string());
+                        Ok(config)
+                    })?;
+                    Ok(())
+                },
+            )?)
         }
         ExecuteMsg::UpdateCommunityAddr { community_addr } => {
             execute_update_community_addr(deps, env, info, community_addr)
@@ -115,9 +176,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::UpdateDayFactorInSeconds {
             day_factor_in_secondsc,
         } => execute_update_day_factor_in_seconds(deps, info, day_factor_in_secondsc),
-        ExecuteMsg::Claim { duration } => execute_claim(deps, env, info.sender, duration),
-        ExecuteMsg::Restake { duration } => execute_restake(deps, env, info.sender, duration),
+        ExecuteMsg::Claim { duration } => {
+            PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Claim)?;
+            execute_claim(deps, env, info.sender, duration)
+        }
+        ExecuteMsg::Restake { duration } => {
+            PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Deposit)?;
+            execute_restake(deps, env, info.sender, duration)
+        }
         ExecuteMsg::UnstakeAndClaim { duration } => {
+            PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Withdraw)?;
             execute_unstake(deps, env, info.sender, duration)
         }
         ExecuteMsg::UpdateSynePowerConstant {
@@ -127,6 +195,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::DepositInVaultAddress { amount } => {
             execute_deposit_in_vault_address(deps, env, info, amount)
         }
+        ExecuteMsg::Pause { operations } => {
+            let is_owner = info.sender == CONFIG.load(deps.storage)?.owner_addr;
+            Ok(PAUSABLE.execute_pause(deps, info, is_owner, operations)?)
+        }
+        ExecuteMsg::Unpause { operations } => {
+            let is_owner = info.sender == CONFIG.load(deps.storage)?.owner_addr;
+            Ok(PAUSABLE.execute_unpause(deps, info, is_owner, operations)?)
+        }
+        ExecuteMsg::UpdateGuardian { guardian } => {
+            let is_owner = info.sender == CONFIG.load(deps.storage)?.owner_addr;
+            let guardian = guardian.map(|g| deps.api.addr_validate(&g)).transpose()?;
+            Ok(PAUSABLE.execute_update_guardian(deps, info, is_owner, guardian)?)
+        }
     }
 }
 
@@ -135,10 +216,10 @@ pub fn execute_deposit_in_vault_address(
     env: Env,
     info: MessageInfo,
     amount: Uint128,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
     let callback: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
         //sending reward to user
@@ -160,7 +241,7 @@ pub fn receive_cw20(
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let contract_addr = info.sender.to_string();
     // let contract_addr = cw20_msg.sender.clone();
     match from_binary(&cw20_msg.msg)? {
@@ -174,9 +255,11 @@ pub fn receive_cw20(
             }
 
             if !authorized {
-                return Err(StdError::generic_err("unauthorized"));
+                return Err(StdError::generic_err("unauthorized").into());
             }
 
+            PAUSABLE.assert_not_paused(deps.storage, PausableOperation::Deposit)?;
+
             execute_stake(
                 deps,
                 env,
@@ -195,64 +278,25 @@ pub fn receive_cw20(
             }
 
             if !authorized {
-                return Err(StdError::generic_err("unauthorized"));
+                return Err(StdError::generic_err("unauthorized").into());
             }
             execute_deposit(deps, env, cw20_msg.amount)
         }
     }
 }
 
-// Only owner can execute it.
-pub fn execute_update_config(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    owner: String,
-) -> StdResult<Response> {
-    let mut config: Config = CONFIG.load(deps.storage)?;
-
-    // permission check
-    if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
-    }
-
-    config.owner_addr = deps.api.addr_validate(&owner)?;
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("action", "update_config"))
-}
-
-// Only owner can execute it.
-pub fn execute_update_second_owner(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    second_owner: String,
-) -> StdResult<Response> {
-    let mut config: Config = CONFIG.load(deps.storage)?;
-
-    // permission check
-    if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
-    }
-
-    config.second_owner = Some(deps.api.addr_validate(&second_owner)?.to_string());
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("action", "update_second_owner"))
-}
 // Only owner can execute it.
 pub fn execute_update_community_addr(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     community_addr: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     config.community_addr = Some(deps.api.addr_validate(&community_addr)?);
@@ -267,12 +311,12 @@ pub fn execute_update_freeze_flag(
     env: Env,
     info: MessageInfo,
     freeze_flag: bool,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     config.freeze = freeze_flag;
@@ -289,12 +333,12 @@ pub fn execute_update_syne_power_constant(
     deps: DepsMut,
     info: MessageInfo,
     syne_power_constant: u128,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     config.syne_power_constant = syne_power_constant;
@@ -310,12 +354,12 @@ pub fn execute_update_freeze_lock_time(
     _env: Env,
     info: MessageInfo,
     freeze_lock_time: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     config.freeze_lock_time = freeze_lock_time;
@@ -330,12 +374,12 @@ pub fn execute_update_lock_time_frame(
     _env: Env,
     info: MessageInfo,
     lock_time_frame: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     config.lock_time_frame = lock_time_frame;
@@ -349,12 +393,12 @@ pub fn execute_add_new_duration(
     deps: DepsMut,
     info: MessageInfo,
     duration: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
     if !config.duration_values_vector.contains(&duration) {
         config.duration_values_vector.push(duration);
@@ -369,12 +413,12 @@ pub fn execute_update_wait_time_for_distributions(
     _env: Env,
     info: MessageInfo,
     wait_time_for_distribution_in_seconds: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
     config.wait_time_for_distribution_in_seconds = wait_time_for_distribution_in_seconds;
     CONFIG.save(deps.storage, &config)?;
@@ -388,12 +432,12 @@ pub fn execute_update_restake_reset_flag(
     _env: Env,
     info: MessageInfo,
     flag: bool,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
 
     config.restake_reset_flag = flag;
@@ -403,7 +447,7 @@ pub fn execute_update_restake_reset_flag(
 }
 
 // Allow admin to deposit reward tokens.
-pub fn execute_deposit(deps: DepsMut, _env: Env, amount: Uint128) -> StdResult<Response> {
+pub fn execute_deposit(deps: DepsMut, _env: Env, amount: Uint128) -> Result<Response, ContractError> {
     let mut total_reward_in_contract = TOTAL_REWARD_IN_CONTRACT.load(deps.storage)?;
     total_reward_in_contract += amount;
     TOTAL_REWARD_IN_CONTRACT.save(deps.storage, &total_reward_in_contract)?;
@@ -418,7 +462,7 @@ pub fn execute_stake(
     sender: Addr,
     amount: Uint128,
     duration: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let current_reward_index = REWARD_INDEX.load(deps.storage)?;
     let key: String = sender.to_string();
     let mut config = CONFIG.load(deps.storage)?;
@@ -433,7 +477,7 @@ pub fn execute_stake(
     if !config.duration_values_vector.contains(&duration) {
         return Err(StdError::generic_err(
             "Invalid duration parameter is passed",
-        ));
+        ).into());
     }
     //reward token issued to be used during evaluation of user share in queries
     let user_info = MINT_TIME
@@ -545,7 +589,7 @@ pub fn execute_restake(
     env: Env,
     sender: Addr,
     duration: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
     let current_reward_index = REWARD_INDEX.load(deps.storage)?;
     let key: String = sender.to_string();
@@ -583,7 +627,7 @@ pub fn execute_restake(
     user_reward_info.reward_index = current_reward_index.pool_reward_index;
     let reward_to_add = user_reward_response.user_reward + user_reward_info.pending_reward;
     if reward_to_add.is_zero() {
-        return Err(StdError::generic_err("None reward for restaking."));
+        return Err(StdError::generic_err("None reward for restaking.").into());
     }
 
     TOTAL_REWARD.update(deps.storage, |mut reward| -> StdResult<_> {
@@ -660,7 +704,7 @@ pub fn execute_unstake(
     env: Env,
     receiver: Addr,
     duration: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     if config.clone().freeze
         && env.block.time.seconds()
@@ -668,7 +712,7 @@ pub fn execute_unstake(
     {
         return Err(StdError::generic_err(
             "Sorry for inconvenience, system is under maintenance. Kindly check again later",
-        ));
+        ).into());
     }
 
     let key: String = receiver.to_string();
@@ -677,13 +721,13 @@ pub fn execute_unstake(
         if let Some(user_info) = MINT_TIME.may_load(deps.storage, (&receiver, duration))? {
             user_info
         } else {
-            return Err(StdError::generic_err("No staked amount found"));
+            return Err(StdError::generic_err("No staked amount found").into());
         };
 
     if env.block.time.seconds() - user_info.mint_time < config.clone().lock_time_frame * duration {
         return Err(StdError::generic_err(
             "The rewards are still locked. Please wait patiently for the specified time",
-        ));
+        ).into());
     }
 
     let contract_addr = config.clone().token_addr;
@@ -808,7 +852,7 @@ pub fn execute_claim(
     env: Env,
     receiver: Addr,
     duration: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     if config.clone().freeze
         && env.block.time.seconds()
@@ -816,7 +860,7 @@ pub fn execute_claim(
     {
         return Err(StdError::generic_err(
             "Sorry for inconvenience, system is under maintenance. Kindly check again later",
-        ));
+        ).into());
     }
 
     let key: String = receiver.to_string();
@@ -825,7 +869,7 @@ pub fn execute_claim(
         if let Some(user_info) = MINT_TIME.may_load(deps.storage, (&receiver, duration))? {
             user_info
         } else {
-            return Err(StdError::generic_err("No Staked Amount found"));
+            return Err(StdError::generic_err("No Staked Amount found").into());
         };
 
     let contract_addr = config.clone().token_addr;
@@ -915,7 +959,7 @@ pub fn execute_claim(
 }
 
 //This will distribute the reward.
-pub fn execute_distribute(deps: DepsMut, env: Env, _info: MessageInfo) -> StdResult<Response> {
+pub fn execute_distribute(deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     //let mut total_staked = TOTAL_ACTIVE_STAKED.load(deps.storage)?;
 
@@ -931,7 +975,7 @@ pub fn execute_distribute(deps: DepsMut, env: Env, _info: MessageInfo) -> StdRes
     if non_distributed_days <= 0 {
         return Err(StdError::generic_err(
             "please wait patiently for the specified time",
-        ));
+        ).into());
     }
     let previous_days =
         (env.block.time.seconds() - config.latest_syne_power_date) / config.day_factor_in_seconds;
@@ -1010,7 +1054,7 @@ pub fn execute_distribute(deps: DepsMut, env: Env, _info: MessageInfo) -> StdRes
                 REWARD_INDEX.save(deps.storage, &reward_index)?;
                 TOTAL_REWARD.save(deps.storage, &total_reward)?;
             } else {
-                return Err(StdError::generic_err("insufficient funds"));
+                return Err(StdError::generic_err("insufficient funds").into());
             }
 
             if config.latest_syne_power_date + config.day_factor_in_seconds
@@ -1048,14 +1092,14 @@ pub fn execute_update_reward(
     _env: Env,
     info: MessageInfo,
     amount: Uint128,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner_addr
         && (config.second_owner.clone().unwrap().is_empty()
             || (!config.second_owner.clone().unwrap().is_empty()
                 && info.sender != config.clone().second_owner.unwrap()))
     {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
     DISTRIBUTION_REWARD.save(deps.storage, &amount)?;
 
@@ -1066,10 +1110,10 @@ pub fn execute_update_day_factor_in_seconds(
     deps: DepsMut,
     info: MessageInfo,
     day_factor_in_seconds: u64,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
     config.day_factor_in_seconds = day_factor_in_seconds;
     CONFIG.save(deps.storage, &config)?;
@@ -1190,6 +1234,13 @@ pub fn execute_update_day_factor_in_seconds(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
+        QueryMsg::IsPaused {} => to_binary(&PAUSABLE.query_is_paused(deps)?),
+        QueryMsg::OwnershipProposal {} => {
+            to_binary(&OWNERSHIP_PROPOSAL.query_ownership_proposal(deps)?)
+        }
+        QueryMsg::SecondOwnershipProposal {} => {
+            to_binary(&SECOND_OWNERSHIP_PROPOSAL.query_ownership_proposal(deps)?)
+        }
         QueryMsg::QueryTotalReward {} => to_binary(&query_total_reward(deps, env)?),
         QueryMsg::QueryTotalRewardInContract {} => {
             to_binary(&query_total_reward_in_contract(deps, env)?)
@@ -1,7 +1,8 @@
 pub mod contract;
+pub mod error;
 pub mod minter;
 pub mod msg;
 pub mod state;
 
-// #[cfg(test)]
-// mod testing;
+#[cfg(test)]
+mod testing;
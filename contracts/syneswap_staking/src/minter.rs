@@ -10,6 +10,7 @@ use cw20::{
     TokenInfoResponse,
 };
 
+use crate::error::ContractError;
 use crate::msg::{Cw20QueryMsg, TokenInstantiateMsg};
 use cw20_base::enumerable::query_all_accounts;
 
@@ -163,11 +164,11 @@ pub fn update_token_info(
     info: MessageInfo,
     name: String,
     symbol: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     // permission check
     let config: Config = CONFIG.load(deps.storage)?;
     if info.sender != config.owner_addr {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(StdError::generic_err("unauthorized").into());
     }
     let mut token_info = TOKEN_INFO.load(deps.storage)?;
 
@@ -1,5 +1,6 @@
 use cosmwasm_std::{StdError, StdResult, Uint128};
 use cw20::Cw20ReceiveMsg;
+use cw_controllers::PausableOperation;
 use cw20::{Cw20Coin, Logo, MinterResponse};
 
 use schemars::JsonSchema;
@@ -129,12 +130,18 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    UpdateConfig {
+    ProposeNewOwner {
         owner: String,
+        expires_in: u64,
     },
-    UpdateSecondOwner {
+    DropOwnershipProposal {},
+    ClaimOwnership {},
+    ProposeNewSecondOwner {
         second_owner: String,
+        expires_in: u64,
     },
+    DropSecondOwnerProposal {},
+    ClaimSecondOwnership {},
     UpdateTokenInfo {
         name: String,
         symbol: String,
@@ -183,6 +190,17 @@ pub enum ExecuteMsg {
     DepositInVaultAddress {
         amount: Uint128,
     },
+    /// Halts operations, callable by the owner or the guardian
+    Pause {
+        operations: Vec<PausableOperation>,
+    },
+    /// Resumes operations, callable by the owner
+    Unpause {
+        operations: Vec<PausableOperation>,
+    },
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -195,6 +213,9 @@ pub enum Cw20HookMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    IsPaused {},
+    OwnershipProposal {},
+    SecondOwnershipProposal {},
     QueryTotalReward {},
     QueryTotalRewardInContract {},
     QueryStakedByUser {
@@ -1,5 +1,6 @@
 use cosmwasm_std::{Addr, Uint128};
 use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
+use cw_controllers::{Ownership, Pausable};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -70,6 +71,9 @@ pub struct PoolRewardIndex {
 
 // put the length bytes at the first for compatibility with legacy singleton store
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const PAUSABLE: Pausable = Pausable::new("pausable");
+pub const OWNERSHIP_PROPOSAL: Ownership = Ownership::new("ownership_proposal");
+pub const SECOND_OWNERSHIP_PROPOSAL: Ownership = Ownership::new("second_ownership_proposal");
 // pub const TOTAL_ACTUAL_STAKED: Item<Uint128> = Item::new("total_actual_staked");
 pub const TOTAL_STAKED_DURATION_WISE: Map<u64, Uint128> = Map::new("total_staked_duration");
 pub const TOTAL_REWARD: Item<Uint128> = Item::new("total_reward");
@@ -0,0 +1,66 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{Addr, OwnedDeps};
+use cw_controllers::OwnershipError;
+
+use crate::contract::{execute, instantiate};
+use crate::msg::{ExecuteMsg, InstantiateMsg, TokenInstantiateMsg};
+use crate::state::CONFIG;
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            token: "syne".to_string(),
+            lock_time_frame: 86400,
+            freeze_lock_time: 86400,
+            restake_reset_flag: false,
+            vault_address: "vault".to_string(),
+            token_instantiate_msg: TokenInstantiateMsg {
+                name: "Staked SYNE".to_string(),
+                symbol: "sSYNE".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: None,
+                marketing: None,
+            },
+        },
+    )
+    .unwrap();
+    deps
+}
+
+#[test]
+fn claimed_ownership_moves_the_config_owner() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::ProposeNewOwner {
+            owner: "new_owner".to_string(),
+            expires_in: 100,
+        },
+    )
+    .unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("new_owner", &[]), ExecuteMsg::ClaimOwnership {}).unwrap();
+    assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap().owner_addr, Addr::unchecked("new_owner"));
+
+    // the previous owner lost its rights
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner", &[]),
+        ExecuteMsg::ProposeNewSecondOwner {
+            second_owner: "second_owner".to_string(),
+            expires_in: 100,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, OwnershipError::Unauthorized {}.into());
+}
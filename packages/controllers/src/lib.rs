@@ -13,11 +13,21 @@ goes for smaller scale units.
 Supported controllers:
 
 * Admin (`UpdateAdmin` handler, `Admin` querier, set_admin and is_admin methods)
+* Ownership (two-step `ProposeNewOwner`/`ClaimOwnership` handlers with expiring proposals)
+* Pausable (`Pause`/`Unpause` handlers for a guardian, `IsPaused` querier, assert_not_paused method)
 */
 mod admin;
 mod claim;
 mod hooks;
+mod ownership;
+mod pausable;
 
 pub use admin::{Admin, AdminError, AdminResponse};
 pub use claim::{Claim, Claims, ClaimsResponse};
 pub use hooks::{HookError, Hooks, HooksResponse};
+pub use ownership::{
+    Ownership, OwnershipError, OwnershipProposal, OwnershipProposalResponse, MAX_PROPOSAL_TTL,
+};
+pub use pausable::{
+    IsPausedResponse, Pausable, PausableError, PausableOperation, PausedOperations,
+};
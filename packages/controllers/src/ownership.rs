@@ -0,0 +1,337 @@
+use schemars::JsonSchema;
+use std::fmt;
+use thiserror::Error;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    attr, Addr, CustomQuery, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_storage_plus::Item;
+
+/// Longest time a proposed owner has to claim the ownership, two weeks
+pub const MAX_PROPOSAL_TTL: u64 = 1209600;
+
+/// A pending request to change the contract ownership
+#[cw_serde]
+pub struct OwnershipProposal {
+    /// The newly proposed contract owner
+    pub owner: Addr,
+    /// Time until the proposal to change ownership expires
+    pub ttl: u64,
+}
+
+/// Returned from Ownership.query_ownership_proposal()
+#[cw_serde]
+pub struct OwnershipProposalResponse {
+    pub proposal: Option<OwnershipProposal>,
+}
+
+/// Errors returned from Ownership
+#[derive(Error, Debug, PartialEq)]
+pub enum OwnershipError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("New owner cannot be same")]
+    SameOwner {},
+
+    #[error("Parameter expires_in cannot be higher than {0}")]
+    ExpiresInTooHigh(u64),
+
+    #[error("Ownership proposal not found")]
+    ProposalNotFound {},
+
+    #[error("Ownership proposal expired")]
+    ProposalExpired {},
+}
+
+impl From<OwnershipError> for StdError {
+    fn from(err: OwnershipError) -> Self {
+        match err {
+            OwnershipError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+// Ownership changes in two steps: the current owner proposes, the new owner claims.
+// A typo in the proposed address can never lock the contract.
+pub struct Ownership<'a>(Item<'a, OwnershipProposal>);
+
+impl<'a> From<Item<'a, OwnershipProposal>> for Ownership<'a> {
+    fn from(item: Item<'a, OwnershipProposal>) -> Self {
+        Ownership(item)
+    }
+}
+
+impl<'a> Ownership<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        Ownership(Item::new(namespace))
+    }
+
+    /// Creates a new request to change the contract ownership, replacing a pending one.
+    /// Only `owner`, the current owner, can do this.
+    pub fn execute_propose_new_owner<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        env: &Env,
+        owner: &Addr,
+        new_owner: String,
+        expires_in: u64,
+    ) -> Result<Response<C>, OwnershipError>
+    where
+        C: Clone + fmt::Debug + PartialEq + JsonSchema,
+    {
+        if info.sender != *owner {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        if new_owner == *owner {
+            return Err(OwnershipError::SameOwner {});
+        }
+
+        if expires_in > MAX_PROPOSAL_TTL {
+            return Err(OwnershipError::ExpiresInTooHigh(MAX_PROPOSAL_TTL));
+        }
+
+        self.0.save(
+            deps.storage,
+            &OwnershipProposal {
+                owner: new_owner.clone(),
+                ttl: env.block.time.seconds() + expires_in,
+            },
+        )?;
+
+        Ok(Response::new().add_attributes(vec![
+            attr("action", "propose_new_owner"),
+            attr("new_owner", new_owner),
+        ]))
+    }
+
+    /// Removes a pending request to change the contract ownership.
+    /// Only `owner`, the current owner, can do this.
+    pub fn execute_drop_ownership_proposal<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        owner: &Addr,
+    ) -> Result<Response<C>, OwnershipError>
+    where
+        C: Clone + fmt::Debug + PartialEq + JsonSchema,
+    {
+        if info.sender != *owner {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        self.0.remove(deps.storage);
+
+        Ok(Response::new().add_attributes(vec![attr("action", "drop_ownership_proposal")]))
+    }
+
+    /// Claims the ownership for the proposed owner before the proposal expires.
+    /// `set_owner` stores the new owner wherever the contract keeps it.
+    pub fn execute_claim_ownership<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        env: &Env,
+        set_owner: impl FnOnce(DepsMut<Q>, Addr) -> StdResult<()>,
+    ) -> Result<Response<C>, OwnershipError>
+    where
+        C: Clone + fmt::Debug + PartialEq + JsonSchema,
+    {
+        let proposal = self
+            .0
+            .may_load(deps.storage)?
+            .ok_or(OwnershipError::ProposalNotFound {})?;
+
+        if info.sender != proposal.owner {
+            return Err(OwnershipError::Unauthorized {});
+        }
+
+        if env.block.time.seconds() > proposal.ttl {
+            return Err(OwnershipError::ProposalExpired {});
+        }
+
+        self.0.remove(deps.storage);
+
+        set_owner(deps, proposal.owner.clone())?;
+
+        Ok(Response::new().add_attributes(vec![
+            attr("action", "claim_ownership"),
+            attr("new_owner", proposal.owner),
+        ]))
+    }
+
+    pub fn query_ownership_proposal<Q: CustomQuery>(
+        &self,
+        deps: Deps<Q>,
+    ) -> StdResult<OwnershipProposalResponse> {
+        Ok(OwnershipProposalResponse {
+            proposal: self.0.may_load(deps.storage)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::Empty;
+
+    #[test]
+    fn propose_and_claim() {
+        let mut deps = mock_dependencies();
+        let control = Ownership::new("foo");
+        let owner = Addr::unchecked("owner");
+        let env = mock_env();
+
+        // only the owner proposes
+        let err = control
+            .execute_propose_new_owner::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("imposter", &[]),
+                &env,
+                &owner,
+                "imposter".to_string(),
+                100,
+            )
+            .unwrap_err();
+        assert_eq!(OwnershipError::Unauthorized {}, err);
+
+        let err = control
+            .execute_propose_new_owner::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(owner.as_ref(), &[]),
+                &env,
+                &owner,
+                owner.to_string(),
+                100,
+            )
+            .unwrap_err();
+        assert_eq!(OwnershipError::SameOwner {}, err);
+
+        let err = control
+            .execute_propose_new_owner::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(owner.as_ref(), &[]),
+                &env,
+                &owner,
+                "new_owner".to_string(),
+                MAX_PROPOSAL_TTL + 1,
+            )
+            .unwrap_err();
+        assert_eq!(OwnershipError::ExpiresInTooHigh(MAX_PROPOSAL_TTL), err);
+
+        control
+            .execute_propose_new_owner::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(owner.as_ref(), &[]),
+                &env,
+                &owner,
+                "new_owner".to_string(),
+                100,
+            )
+            .unwrap();
+        let res = control.query_ownership_proposal(deps.as_ref()).unwrap();
+        assert_eq!(
+            Some(OwnershipProposal {
+                owner: Addr::unchecked("new_owner"),
+                ttl: env.block.time.seconds() + 100,
+            }),
+            res.proposal
+        );
+
+        // only the proposed owner claims
+        let err = control
+            .execute_claim_ownership::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(owner.as_ref(), &[]),
+                &env,
+                |_, _| Ok(()),
+            )
+            .unwrap_err();
+        assert_eq!(OwnershipError::Unauthorized {}, err);
+
+        let mut claimed = None;
+        control
+            .execute_claim_ownership::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("new_owner", &[]),
+                &env,
+                |_, new_owner| {
+                    claimed = Some(new_owner);
+                    Ok(())
+                },
+            )
+            .unwrap();
+        assert_eq!(Some(Addr::unchecked("new_owner")), claimed);
+
+        // the proposal is gone after the claim
+        let res = control.query_ownership_proposal(deps.as_ref()).unwrap();
+        assert_eq!(None, res.proposal);
+    }
+
+    #[test]
+    fn expired_and_dropped_proposals() {
+        let mut deps = mock_dependencies();
+        let control = Ownership::new("foo");
+        let owner = Addr::unchecked("owner");
+        let mut env = mock_env();
+
+        control
+            .execute_propose_new_owner::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(owner.as_ref(), &[]),
+                &env,
+                &owner,
+                "new_owner".to_string(),
+                100,
+            )
+            .unwrap();
+
+        env.block.time = env.block.time.plus_seconds(101);
+        let err = control
+            .execute_claim_ownership::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("new_owner", &[]),
+                &env,
+                |_, _| Ok(()),
+            )
+            .unwrap_err();
+        assert_eq!(OwnershipError::ProposalExpired {}, err);
+
+        // only the owner drops
+        let err = control
+            .execute_drop_ownership_proposal::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("new_owner", &[]),
+                &owner,
+            )
+            .unwrap_err();
+        assert_eq!(OwnershipError::Unauthorized {}, err);
+
+        control
+            .execute_drop_ownership_proposal::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(owner.as_ref(), &[]),
+                &owner,
+            )
+            .unwrap();
+        let err = control
+            .execute_claim_ownership::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("new_owner", &[]),
+                &env,
+                |_, _| Ok(()),
+            )
+            .unwrap_err();
+        assert_eq!(OwnershipError::ProposalNotFound {}, err);
+    }
+}
@@ -0,0 +1,329 @@
+use schemars::JsonSchema;
+use std::fmt;
+use thiserror::Error;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    attr, Addr, CustomQuery, Deps, DepsMut, MessageInfo, Response, StdError, StdResult, Storage,
+};
+use cw_storage_plus::Item;
+
+/// Operations that can be halted independently of each other
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum PausableOperation {
+    Deposit,
+    Withdraw,
+    Claim,
+    Convert,
+}
+
+impl fmt::Display for PausableOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PausableOperation::Deposit => write!(f, "deposit"),
+            PausableOperation::Withdraw => write!(f, "withdraw"),
+            PausableOperation::Claim => write!(f, "claim"),
+            PausableOperation::Convert => write!(f, "convert"),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default, Eq)]
+pub struct PausedOperations {
+    pub deposit: bool,
+    pub withdraw: bool,
+    pub claim: bool,
+    pub convert: bool,
+}
+
+impl PausedOperations {
+    pub fn is_paused(&self, operation: PausableOperation) -> bool {
+        match operation {
+            PausableOperation::Deposit => self.deposit,
+            PausableOperation::Withdraw => self.withdraw,
+            PausableOperation::Claim => self.claim,
+            PausableOperation::Convert => self.convert,
+        }
+    }
+
+    fn set(&mut self, operation: PausableOperation, paused: bool) {
+        match operation {
+            PausableOperation::Deposit => self.deposit = paused,
+            PausableOperation::Withdraw => self.withdraw = paused,
+            PausableOperation::Claim => self.claim = paused,
+            PausableOperation::Convert => self.convert = paused,
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+struct PauseState {
+    guardian: Option<Addr>,
+    paused: PausedOperations,
+}
+
+/// Returned from Pausable.query_is_paused()
+#[cw_serde]
+pub struct IsPausedResponse {
+    pub guardian: Option<String>,
+    pub paused: PausedOperations,
+}
+
+/// Errors returned from Pausable
+#[derive(Error, Debug, PartialEq)]
+pub enum PausableError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Caller is neither guardian nor admin")]
+    NotGuardian {},
+
+    #[error("Caller is not admin")]
+    NotAdmin {},
+
+    #[error("Operation {0} is paused")]
+    Paused(PausableOperation),
+}
+
+// The guardian can only halt operations, resuming them is left to the admin.
+// Contracts that never stored anything here are not paused.
+pub struct Pausable<'a>(Item<'a, PauseState>);
+
+impl<'a> Pausable<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        Pausable(Item::new(namespace))
+    }
+
+    fn state(&self, storage: &dyn Storage) -> StdResult<PauseState> {
+        Ok(self.0.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn set_guardian(&self, storage: &mut dyn Storage, guardian: Option<Addr>) -> StdResult<()> {
+        let mut state = self.state(storage)?;
+        state.guardian = guardian;
+        self.0.save(storage, &state)
+    }
+
+    pub fn guardian<Q: CustomQuery>(&self, deps: Deps<Q>) -> StdResult<Option<Addr>> {
+        Ok(self.state(deps.storage)?.guardian)
+    }
+
+    pub fn is_paused(&self, storage: &dyn Storage, operation: PausableOperation) -> StdResult<bool> {
+        Ok(self.state(storage)?.paused.is_paused(operation))
+    }
+
+    /// Like is_paused but returns PausableError::Paused if the operation is halted.
+    /// Helper for a nice one-line check at the top of an entry point.
+    pub fn assert_not_paused(
+        &self,
+        storage: &dyn Storage,
+        operation: PausableOperation,
+    ) -> Result<(), PausableError> {
+        if self.is_paused(storage, operation)? {
+            Err(PausableError::Paused(operation))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Halts the given operations. `is_admin` tells if the caller is the admin of the contract.
+    pub fn execute_pause<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        is_admin: bool,
+        operations: Vec<PausableOperation>,
+    ) -> Result<Response<C>, PausableError>
+    where
+        C: Clone + fmt::Debug + PartialEq + JsonSchema,
+    {
+        let mut state = self.state(deps.storage)?;
+        if !is_admin && state.guardian.as_ref() != Some(&info.sender) {
+            return Err(PausableError::NotGuardian {});
+        }
+
+        let mut attributes = vec![attr("action", "pause"), attr("sender", info.sender)];
+        for operation in operations {
+            state.paused.set(operation, true);
+            attributes.push(attr("operation", operation.to_string()));
+        }
+        self.0.save(deps.storage, &state)?;
+
+        Ok(Response::new().add_attributes(attributes))
+    }
+
+    /// Resumes the given operations. Only the admin of the contract can do this.
+    pub fn execute_unpause<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        is_admin: bool,
+        operations: Vec<PausableOperation>,
+    ) -> Result<Response<C>, PausableError>
+    where
+        C: Clone + fmt::Debug + PartialEq + JsonSchema,
+    {
+        if !is_admin {
+            return Err(PausableError::NotAdmin {});
+        }
+
+        let mut state = self.state(deps.storage)?;
+        let mut attributes = vec![attr("action", "unpause"), attr("sender", info.sender)];
+        for operation in operations {
+            state.paused.set(operation, false);
+            attributes.push(attr("operation", operation.to_string()));
+        }
+        self.0.save(deps.storage, &state)?;
+
+        Ok(Response::new().add_attributes(attributes))
+    }
+
+    pub fn execute_update_guardian<C, Q: CustomQuery>(
+        &self,
+        deps: DepsMut<Q>,
+        info: MessageInfo,
+        is_admin: bool,
+        guardian: Option<Addr>,
+    ) -> Result<Response<C>, PausableError>
+    where
+        C: Clone + fmt::Debug + PartialEq + JsonSchema,
+    {
+        if !is_admin {
+            return Err(PausableError::NotAdmin {});
+        }
+
+        let guardian_str = match guardian.as_ref() {
+            Some(guardian) => guardian.to_string(),
+            None => "None".to_string(),
+        };
+        let attributes = vec![
+            attr("action", "update_guardian"),
+            attr("guardian", guardian_str),
+            attr("sender", info.sender),
+        ];
+
+        self.set_guardian(deps.storage, guardian)?;
+
+        Ok(Response::new().add_attributes(attributes))
+    }
+
+    pub fn query_is_paused<Q: CustomQuery>(&self, deps: Deps<Q>) -> StdResult<IsPausedResponse> {
+        let state = self.state(deps.storage)?;
+        Ok(IsPausedResponse {
+            guardian: state.guardian.map(String::from),
+            paused: state.paused,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_info};
+    use cosmwasm_std::Empty;
+
+    #[test]
+    fn nothing_paused_by_default() {
+        let deps = mock_dependencies();
+        let control = Pausable::new("foo");
+
+        for operation in [
+            PausableOperation::Deposit,
+            PausableOperation::Withdraw,
+            PausableOperation::Claim,
+            PausableOperation::Convert,
+        ] {
+            control
+                .assert_not_paused(deps.as_ref().storage, operation)
+                .unwrap();
+        }
+
+        let res = control.query_is_paused(deps.as_ref()).unwrap();
+        assert_eq!(None, res.guardian);
+        assert_eq!(PausedOperations::default(), res.paused);
+    }
+
+    #[test]
+    fn guardian_pauses_admin_unpauses() {
+        let mut deps = mock_dependencies();
+        let control = Pausable::new("foo");
+        let guardian = Addr::unchecked("guardian");
+        let imposter = Addr::unchecked("imposter");
+
+        // only the admin sets the guardian
+        let err = control
+            .execute_update_guardian::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(imposter.as_ref(), &[]),
+                false,
+                Some(imposter.clone()),
+            )
+            .unwrap_err();
+        assert_eq!(PausableError::NotAdmin {}, err);
+        control
+            .execute_update_guardian::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("admin", &[]),
+                true,
+                Some(guardian.clone()),
+            )
+            .unwrap();
+        assert_eq!(Some(guardian.clone()), control.guardian(deps.as_ref()).unwrap());
+
+        // imposter cannot pause
+        let err = control
+            .execute_pause::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(imposter.as_ref(), &[]),
+                false,
+                vec![PausableOperation::Withdraw],
+            )
+            .unwrap_err();
+        assert_eq!(PausableError::NotGuardian {}, err);
+
+        // guardian pauses a single operation
+        control
+            .execute_pause::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(guardian.as_ref(), &[]),
+                false,
+                vec![PausableOperation::Withdraw],
+            )
+            .unwrap();
+        let err = control
+            .assert_not_paused(deps.as_ref().storage, PausableOperation::Withdraw)
+            .unwrap_err();
+        assert_eq!(PausableError::Paused(PausableOperation::Withdraw), err);
+        control
+            .assert_not_paused(deps.as_ref().storage, PausableOperation::Deposit)
+            .unwrap();
+
+        // guardian cannot resume
+        let err = control
+            .execute_unpause::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info(guardian.as_ref(), &[]),
+                false,
+                vec![PausableOperation::Withdraw],
+            )
+            .unwrap_err();
+        assert_eq!(PausableError::NotAdmin {}, err);
+
+        // admin resumes
+        control
+            .execute_unpause::<Empty, Empty>(
+                deps.as_mut(),
+                mock_info("admin", &[]),
+                true,
+                vec![PausableOperation::Withdraw],
+            )
+            .unwrap();
+        let res = control.query_is_paused(deps.as_ref()).unwrap();
+        assert_eq!(Some(guardian.to_string()), res.guardian);
+        assert_eq!(PausedOperations::default(), res.paused);
+    }
+}
@@ -2,6 +2,13 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, AttributeArgs, DataEnum, DeriveInput, Variant};
 
+/// Whether one of the attributes following the macro derives `QueryResponses`
+fn derives_query_responses(ast: &DeriveInput) -> bool {
+    ast.attrs.iter().any(|attr| {
+        attr.path.is_ident("derive") && attr.tokens.to_string().contains("QueryResponses")
+    })
+}
+
 /// Adds the nesecary fields to an such that the enum implements the
 /// interface needed to be a cw-governance voting module.
 ///
@@ -29,6 +36,10 @@ use syn::{parse_macro_input, AttributeArgs, DataEnum, DeriveInput, Variant};
 /// }
 /// ```
 ///
+/// If the enum derives `QueryResponses`, the new fields are tagged with
+/// their `#[returns(..)]` types from `cw_core_interface::voting`, which
+/// then has to be a dependency of the crate.
+///
 /// Note that other derive macro invocations must occur after this
 /// procedural macro as they may depend on the new fields. For
 /// example, the following will fail becase the `Clone` derivation
@@ -57,20 +68,33 @@ pub fn voting_query(metadata: TokenStream, input: TokenStream) -> TokenStream {
     }
 
     let mut ast: DeriveInput = parse_macro_input!(input);
+    let query_responses = derives_query_responses(&ast);
     match &mut ast.data {
         syn::Data::Enum(DataEnum { variants, .. }) => {
-            let voting_power: Variant = syn::parse2(quote! { VotingPowerAtHeight {
+            let mut voting_power: Variant = syn::parse2(quote! { VotingPowerAtHeight {
                 address: ::std::string::String,
                 height: ::std::option::Option<::std::primitive::u64>
             } })
             .unwrap();
 
-            let total_power: Variant = syn::parse2(quote! { TotalPowerAtHeight {
+            let mut total_power: Variant = syn::parse2(quote! { TotalPowerAtHeight {
                 height: ::std::option::Option<::std::primitive::u64>
             } })
             .unwrap();
 
-            let info: Variant = syn::parse2(quote! { Info {} }).unwrap();
+            let mut info: Variant = syn::parse2(quote! { Info {} }).unwrap();
+
+            if query_responses {
+                voting_power.attrs.push(syn::parse_quote! {
+                    #[returns(::cw_core_interface::voting::VotingPowerAtHeightResponse)]
+                });
+                total_power.attrs.push(syn::parse_quote! {
+                    #[returns(::cw_core_interface::voting::TotalPowerAtHeightResponse)]
+                });
+                info.attrs.push(syn::parse_quote! {
+                    #[returns(::cw_core_interface::voting::InfoResponse)]
+                });
+            }
 
             variants.push(voting_power);
             variants.push(total_power);
@@ -1,11 +1,20 @@
+use cosmwasm_std::Uint128;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    /// Starts the unbonding process for the given number of tokens
+    Unbond {
+        tokens: Uint128,
+        /// Unbonding period in seconds
+        unbonding_period: u64,
+    },
+    /// Claims all tokens whose unbonding period has passed
+    Claim {},
     WithdrawRewards {
         owner: Option<String>,
         receiver: Option<String>,
     },
-}
\ No newline at end of file
+}
@@ -1,17 +1,8 @@
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{attr, Addr, Api, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cosmwasm_std::{Addr, Api, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw_controllers::Ownership;
 use cw_storage_plus::Item;
 
-const MAX_PROPOSAL_TTL: u64 = 1209600;
-
-/// This structure describes the parameters used for creating a request for a change of contract ownership.
-#[cw_serde]
-pub struct OwnershipProposal {
-    /// The newly proposed contract owner
-    pub owner: Addr,
-    /// Time until the proposal to change ownership expires
-    pub ttl: u64,
-}
+pub use cw_controllers::OwnershipProposal;
 
 /// Creates a new request to change contract ownership.
 ///
@@ -32,37 +23,9 @@ pub fn propose_new_owner(
     owner: Addr,
     proposal: Item<OwnershipProposal>,
 ) -> StdResult<Response> {
-    // Permission check
-    if info.sender != owner {
-        return Err(StdError::generic_err("Unauthorized"));
-    }
-
-    let new_owner = deps.api.addr_validate(new_owner.as_str())?;
-
-    // Check that the new owner is not the same as the current one
-    if new_owner == owner {
-        return Err(StdError::generic_err("New owner cannot be same"));
-    }
-
-    if MAX_PROPOSAL_TTL < expires_in {
-        return Err(StdError::generic_err(format!(
-            "Parameter expires_in cannot be higher than {}",
-            MAX_PROPOSAL_TTL
-        )));
-    }
-
-    proposal.save(
-        deps.storage,
-        &OwnershipProposal {
-            owner: new_owner.clone(),
-            ttl: env.block.time.seconds() + expires_in,
-        },
-    )?;
-
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "propose_new_owner"),
-        attr("new_owner", new_owner),
-    ]))
+    Ok(Ownership::from(proposal).execute_propose_new_owner(
+        deps, info, &env, &owner, new_owner, expires_in,
+    )?)
 }
 
 /// Removes a request to change contract ownership.
@@ -76,14 +39,7 @@ pub fn drop_ownership_proposal(
     owner: Addr,
     proposal: Item<OwnershipProposal>,
 ) -> StdResult<Response> {
-    // Permission check
-    if info.sender != owner {
-        return Err(StdError::generic_err("Unauthorized"));
-    }
-
-    proposal.remove(deps.storage);
-
-    Ok(Response::new().add_attributes(vec![attr("action", "drop_ownership_proposal")]))
+    Ok(Ownership::from(proposal).execute_drop_ownership_proposal(deps, info, &owner)?)
 }
 
 /// Claims ownership over the contract.
@@ -99,28 +55,7 @@ pub fn claim_ownership(
     proposal: Item<OwnershipProposal>,
     cb: fn(DepsMut, Addr) -> StdResult<()>,
 ) -> StdResult<Response> {
-    let p = proposal
-        .load(deps.storage)
-        .map_err(|_| StdError::generic_err("Ownership proposal not found"))?;
-
-    // Check the sender
-    if info.sender != p.owner {
-        return Err(StdError::generic_err("Unauthorized"));
-    }
-
-    if env.block.time.seconds() > p.ttl {
-        return Err(StdError::generic_err("Ownership proposal expired"));
-    }
-
-    proposal.remove(deps.storage);
-
-    // Run callback
-    cb(deps, p.owner.clone())?;
-
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "claim_ownership"),
-        attr("new_owner", p.owner),
-    ]))
+    Ok(Ownership::from(proposal).execute_claim_ownership(deps, info, &env, cb)?)
 }
 
 /// Bulk validation and conversion between [`String`] -> [`Addr`] for an array of addresses.
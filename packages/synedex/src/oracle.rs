@@ -180,7 +180,7 @@ fn cartesian_product(
 /// The map does not give a lot of guarantees, except that all valid entries are ordered by their timestamp.
 /// There are, however, possibly invalid entries stored. These are entries that are older than the start time.
 /// These are not removed, but filtered out when loading the buffer.
-struct TimeBuffer<'a, T: Serialize + DeserializeOwned, const STEP: u64, const CAP: u64> {
+pub struct TimeBuffer<'a, T: Serialize + DeserializeOwned, const STEP: u64, const CAP: u64> {
     /// The actual data. The value contains both the timestamp and the value.
     data: Map<'a, u64, (u64, T)>,
     /// The start index. This is subtracted from all indices to get the actual index.
@@ -5,6 +5,7 @@ use crate::asset::StakeableToken;
 
 use cosmwasm_std::Uint128;
 use cw20::Cw20ReceiveMsg;
+use cw_controllers::PausableOperation;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -16,9 +17,12 @@ pub struct InstantiateMsg {
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
-    UpdateConfig {
-        owner: Option<String>,
+    ProposeNewOwner {
+        owner: String,
+        expires_in: u64,
     },
+    DropOwnershipProposal {},
+    ClaimOwnership {},
     UpdateTreasuryAddr {
         treasury_addr: String,
     },
@@ -49,7 +53,18 @@ pub enum ExecuteMsg {
     WithdrawTreasuryReward {
         token: String,
         amount: Uint128
-    }
+    },
+    /// Halts operations, callable by the owner or the guardian
+    Pause {
+        operations: Vec<PausableOperation>,
+    },
+    /// Resumes operations, callable by the owner
+    Unpause {
+        operations: Vec<PausableOperation>,
+    },
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -85,6 +100,8 @@ pub enum LoopFarmExecuteMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+    IsPaused {},
+    OwnershipProposal {},
     QueryRewardInPool {
         pool: String,
         distribution_token: String,